@@ -14,9 +14,9 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
-/// All occurences of String are replaced with i64 for convenience.
-/// We could theoretically have different ASTs for different phases of the CNF transformation.
-/// However, it seems to me that some invariants are impossible to capture.
+// All occurences of String are replaced with i64 for convenience.
+// We could theoretically have different ASTs for different phases of the CNF transformation.
+// However, it seems to me that some invariants are impossible to capture.
 
 /// Data type for FOL terms.
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
@@ -16,62 +16,113 @@
 
 use crate::cnf::ast::Formula as CnfFormula;
 use crate::cnf::ast::Term as CnfTerm;
-use crate::cnf::renaming_info::RenamingInfo;
-use crate::tptp_parser::ast::AnnotatedFormula;
+use crate::cnf::formula_origin::FormulaOrigin;
+use crate::cnf::renaming_info::{
+    RenamingInfo, BOOLEAN_SORT, BUILTIN_SORTS, INT_SORT, RAT_SORT, REAL_SORT,
+};
 use crate::tptp_parser::ast::Formula as ParserFormula;
 use crate::tptp_parser::ast::Term as ParserTerm;
-use crate::tptp_parser::parser::parse_tptp_file;
+use crate::tptp_parser::ast::{AnnotatedFormula, Annotations, InputFormula};
+use crate::tptp_parser::ast::{TffFormula, TffType};
+use crate::tptp_parser::include_resolver::IncludeResolver;
+use crate::tptp_parser::parse_error::TptpParseError;
+use crate::tptp_parser::parser::{parse_tptp_file, parse_tptp_str};
+
+/// The axioms and conjectures of a problem in CNF AST form together with the renaming info.
+/// Each formula is paired with the ID of its origin in the renaming info.
+pub type CnfProblem = (
+    Vec<(CnfFormula, usize)>,
+    Vec<(CnfFormula, usize)>,
+    RenamingInfo,
+);
 
 /// Parses the file at the location given by the string into a CNF AST, if possible.
 /// More specifically, the CNF AST is in two parts: axioms and conjectures.
 /// Either one might be empty but not both.
 pub fn tptp_to_cnf_ast(
     s: &str,
-) -> Result<(Vec<CnfFormula>, Vec<CnfFormula>, RenamingInfo), String> {
-    Ok(tptp_ast_to_cnf_ast(parse_tptp_file(s)?)?)
+    resolver: &dyn IncludeResolver,
+) -> Result<CnfProblem, TptpParseError> {
+    tptp_ast_to_cnf_ast(parse_tptp_file(s, resolver)?)
+}
+
+/// Same as `tptp_to_cnf_ast`, except that the problem is given as a string.
+pub fn tptp_str_to_cnf_ast(
+    s: &str,
+    resolver: &dyn IncludeResolver,
+) -> Result<CnfProblem, TptpParseError> {
+    tptp_ast_to_cnf_ast(parse_tptp_str(s, resolver)?)
 }
 
 /// Transforms the AST format of the TPTP parser into the AST format of the CNF transformer.
 /// The first and second elements of the tuple are the axioms and conjectures of the problem.
 /// Either one might be empty, but not both.
-fn tptp_ast_to_cnf_ast(
-    f_list: Vec<AnnotatedFormula>,
-) -> Result<(Vec<CnfFormula>, Vec<CnfFormula>, RenamingInfo), String> {
+fn tptp_ast_to_cnf_ast(f_list: Vec<InputFormula>) -> Result<CnfProblem, TptpParseError> {
     // Check if any of the formula roles is incorrect.
     // Also, we hit the if let borrow bug again.
-    if let Some(f) = f_list.iter().find(|&f| !formula_role_valid(f)) {
+    if let Some((f, _)) = f_list.iter().find(|&f| !formula_role_valid(&f.0)) {
         match *f {
-            AnnotatedFormula::Cnf(_) => return Err(cnf_formula_role_error(f)),
-            AnnotatedFormula::Fof(_) => return Err(fof_formula_role_error(f)),
+            AnnotatedFormula::Cnf(_) => return Err(cnf_formula_role_error(f).into()),
+            AnnotatedFormula::Fof(_) => return Err(fof_formula_role_error(f).into()),
+            AnnotatedFormula::Tff(_) => return Err(tff_formula_role_error(f).into()),
         }
     }
 
     // Check that we don't have both conjectures and negated conjectures in a problem.
     let contains_negated_conj = f_list
         .iter()
-        .any(|x| get_formula_role(x) == "negated_conjecture");
-    let contains_conj = f_list.iter().any(|x| get_formula_role(x) == "conjecture");
+        .any(|x| get_formula_role(&x.0) == "negated_conjecture");
+    let contains_conj = f_list
+        .iter()
+        .any(|x| get_formula_role(&x.0) == "conjecture");
     if contains_negated_conj && contains_conj {
-        return Err("A problem shouldn't have both negated_conjectures and conjectures".to_owned());
+        return Err(TptpParseError::Input(
+            "A problem shouldn't have both negated_conjectures and conjectures".to_owned(),
+        ));
     }
 
+    // Type declarations are handled first so that the sorts are known for all formulas.
+    // Arithmetic is only interpreted in typed problems.
     let mut renaming_info = RenamingInfo::new();
+    if f_list
+        .iter()
+        .any(|x| matches!(x.0, AnnotatedFormula::Tff(_)))
+    {
+        renaming_info.enable_arithmetic();
+    }
+    let (type_decls, f_list): (Vec<_>, Vec<_>) = f_list
+        .into_iter()
+        .partition(|x| get_formula_role(&x.0) == "type");
+    for (decl, _) in type_decls {
+        if let AnnotatedFormula::Tff((_, _, TffFormula::TypeDeclaration(s, ty), _)) = decl {
+            declare_type(s, ty, &mut renaming_info)?;
+        }
+    }
+    if f_list.is_empty() {
+        return Err(TptpParseError::Input(
+            "The problem does not contain any formulas".to_owned(),
+        ));
+    }
+
+    // The origins are recorded in the order of the input, even though conjectures are transformed first.
+    let f_list = f_list
+        .into_iter()
+        .map(|x| record_origin(x, &mut renaming_info))
+        .collect::<Vec<_>>();
     let (conj_annotated, other_annotated): (Vec<_>, Vec<_>) = f_list
         .into_iter()
-        .partition(|x| get_formula_role(x) == "conjecture");
+        .partition(|x| renaming_info.get_formula_origin(x.1).get_role() == "conjecture");
 
     let conj = conj_annotated
         .into_iter()
-        .map(strip_annotations)
-        .map(|x| transform_ast(x, &mut renaming_info))
-        .collect::<Vec<_>>();
+        .map(|(f, id)| transform_input(f, id, &mut renaming_info))
+        .collect::<Result<Vec<_>, _>>()?;
     let other = other_annotated
         .into_iter()
-        .map(strip_annotations)
-        .map(|x| transform_ast(x, &mut renaming_info))
-        .collect::<Vec<_>>();
+        .map(|(f, id)| transform_input(f, id, &mut renaming_info))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    assert!(conj.len() > 0 || other.len() > 0);
+    assert!(!conj.is_empty() || !other.is_empty());
     Ok((other, conj, renaming_info))
 }
 
@@ -95,10 +146,52 @@ fn fof_formula_role_error(f: &AnnotatedFormula) -> String {
     )
 }
 
-/// Strips all annotations from a single annotated formula.
-fn strip_annotations(f: AnnotatedFormula) -> ParserFormula {
+/// Returns an error string for a faulty TFF formula role.
+fn tff_formula_role_error(f: &AnnotatedFormula) -> String {
+    if let AnnotatedFormula::Tff((_, _, TffFormula::TypeDeclaration(..), _)) = *f {
+        format!(
+            "Formula role was expected to be 'type' for a type declaration instead of {}",
+            get_formula_role(f)
+        )
+    } else {
+        format!(
+            "Formula role was expected to be one of \
+                 'axiom|hypothesis|definition|assumption|lemma|theorem|negated_conjecture|conjecture' \
+                 instead of {}",
+            get_formula_role(f)
+        )
+    }
+}
+
+/// Records the origin of an input formula in the renaming info.
+/// Returns the formula together with the ID of its origin.
+fn record_origin(input: InputFormula, ri: &mut RenamingInfo) -> (ParserFormula, usize) {
+    let (name, role, f, annotations) = split_annotations(input.0);
+    let id = ri.add_formula_origin(FormulaOrigin::new(name, role, input.1, annotations));
+    (f, id)
+}
+
+/// Transforms an input formula with the given origin.
+fn transform_input(
+    f: ParserFormula,
+    id: usize,
+    ri: &mut RenamingInfo,
+) -> Result<(CnfFormula, usize), String> {
+    let cnf_f = transform_ast(f, ri)
+        .map_err(|e| format!("{} in formula {}", e, ri.get_formula_origin(id).get_name()))?;
+    Ok((cnf_f, id))
+}
+
+/// Splits an annotated formula into its name, role, formula and annotations.
+fn split_annotations(f: AnnotatedFormula) -> (String, String, ParserFormula, Option<Annotations>) {
     match f {
-        AnnotatedFormula::Cnf(cnf_f) | AnnotatedFormula::Fof(cnf_f) => cnf_f.2,
+        AnnotatedFormula::Cnf(cnf_f) | AnnotatedFormula::Fof(cnf_f) => cnf_f,
+        AnnotatedFormula::Tff((name, role, TffFormula::Formula(f), annotations)) => {
+            (name, role, f, annotations)
+        }
+        AnnotatedFormula::Tff((_, _, TffFormula::TypeDeclaration(..), _)) => {
+            panic!("Type declarations should have been removed already")
+        }
     }
 }
 
@@ -106,6 +199,7 @@ fn strip_annotations(f: AnnotatedFormula) -> ParserFormula {
 fn get_formula_role(f: &AnnotatedFormula) -> String {
     match *f {
         AnnotatedFormula::Cnf(ref cnf_f) | AnnotatedFormula::Fof(ref cnf_f) => cnf_f.1.clone(),
+        AnnotatedFormula::Tff(ref tff_f) => tff_f.1.clone(),
     }
 }
 
@@ -114,6 +208,10 @@ fn formula_role_valid(f: &AnnotatedFormula) -> bool {
     match *f {
         AnnotatedFormula::Cnf(ref cnf_f) => formula_role_valid_cnf(&cnf_f.1),
         AnnotatedFormula::Fof(ref fof_f) => formula_role_valid_fof(&fof_f.1),
+        AnnotatedFormula::Tff((_, ref role, TffFormula::Formula(_), _)) => {
+            formula_role_valid_fof(role)
+        }
+        AnnotatedFormula::Tff((_, ref role, TffFormula::TypeDeclaration(..), _)) => role == "type",
     }
 }
 
@@ -133,103 +231,341 @@ fn formula_role_valid_fof(s: &str) -> bool {
     formula_role_valid_cnf(s) || s == "conjecture"
 }
 
-fn transform_ast(f: ParserFormula, ri: &mut RenamingInfo) -> CnfFormula {
+/// Registers a new sort or the type of a symbol.
+fn declare_type(s: String, ty: TffType, ri: &mut RenamingInfo) -> Result<(), String> {
+    match ty {
+        TffType::Atomic(ref sort) if sort == "$tType" => {
+            ri.get_sort_id(&s);
+        }
+        TffType::Atomic(sort) => {
+            check_sort(&sort)?;
+            ri.declare_function(s, &[], &sort);
+        }
+        TffType::Mapping(args, sort) => {
+            for arg in &args {
+                check_sort(arg)?;
+            }
+            check_sort(&sort)?;
+            ri.declare_function(s, &args, &sort);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a sort is not an unknown defined type.
+fn check_sort(sort: &str) -> Result<(), String> {
+    if sort.starts_with('$') && !BUILTIN_SORTS.contains(&sort) {
+        Err(format!("Unknown defined type {}", sort))
+    } else {
+        Ok(())
+    }
+}
+
+fn transform_ast(f: ParserFormula, ri: &mut RenamingInfo) -> Result<CnfFormula, String> {
     match f {
+        ParserFormula::True => Ok(CnfFormula::True),
+        ParserFormula::False => Ok(CnfFormula::False),
         ParserFormula::Predicate(s, args) => transform_literal(s, args, ri),
-        ParserFormula::Not(p) => CnfFormula::Not(Box::new(transform_ast(*p, ri))),
+        ParserFormula::Not(p) => Ok(CnfFormula::Not(Box::new(transform_ast(*p, ri)?))),
         ParserFormula::And(p, q) => transform_and(*p, *q, ri),
         ParserFormula::Or(p, q) => transform_or(*p, *q, ri),
-        ParserFormula::Implies(p, q) => CnfFormula::Implies(
-            Box::new(transform_ast(*p, ri)),
-            Box::new(transform_ast(*q, ri)),
-        ),
-        ParserFormula::Equivalent(p, q) => CnfFormula::Equivalent(
-            Box::new(transform_ast(*p, ri)),
-            Box::new(transform_ast(*q, ri)),
-        ),
-        ParserFormula::Forall(s, p) => transform_quantifier(s, *p, ri, true),
-        ParserFormula::Exists(s, p) => transform_quantifier(s, *p, ri, false),
+        ParserFormula::Implies(p, q) => Ok(CnfFormula::Implies(
+            Box::new(transform_ast(*p, ri)?),
+            Box::new(transform_ast(*q, ri)?),
+        )),
+        ParserFormula::Equivalent(p, q) => Ok(CnfFormula::Equivalent(
+            Box::new(transform_ast(*p, ri)?),
+            Box::new(transform_ast(*q, ri)?),
+        )),
+        ParserFormula::Forall(s, sort, p) => transform_quantifier(s, &sort, *p, ri, true),
+        ParserFormula::Exists(s, sort, p) => transform_quantifier(s, &sort, *p, ri, false),
     }
 }
 
-fn transform_and(p: ParserFormula, q: ParserFormula, ri: &mut RenamingInfo) -> CnfFormula {
+fn transform_and(
+    p: ParserFormula,
+    q: ParserFormula,
+    ri: &mut RenamingInfo,
+) -> Result<CnfFormula, String> {
     let mut l = Vec::<CnfFormula>::new();
-    collect_and(p, &mut l, ri);
-    collect_and(q, &mut l, ri);
-    CnfFormula::And(l)
+    collect_and(p, &mut l, ri)?;
+    collect_and(q, &mut l, ri)?;
+    Ok(CnfFormula::And(l))
 }
 
-fn collect_and(f: ParserFormula, l: &mut Vec<CnfFormula>, ri: &mut RenamingInfo) {
+fn collect_and(
+    f: ParserFormula,
+    l: &mut Vec<CnfFormula>,
+    ri: &mut RenamingInfo,
+) -> Result<(), String> {
     match f {
         ParserFormula::And(p, q) => {
-            collect_and(*p, l, ri);
+            collect_and(*p, l, ri)?;
             collect_and(*q, l, ri)
         }
-        _ => l.push(transform_ast(f, ri)),
+        _ => {
+            l.push(transform_ast(f, ri)?);
+            Ok(())
+        }
     }
 }
 
-fn transform_or(p: ParserFormula, q: ParserFormula, ri: &mut RenamingInfo) -> CnfFormula {
+fn transform_or(
+    p: ParserFormula,
+    q: ParserFormula,
+    ri: &mut RenamingInfo,
+) -> Result<CnfFormula, String> {
     let mut l = Vec::<CnfFormula>::new();
-    collect_or(p, &mut l, ri);
-    collect_or(q, &mut l, ri);
-    CnfFormula::Or(l)
+    collect_or(p, &mut l, ri)?;
+    collect_or(q, &mut l, ri)?;
+    Ok(CnfFormula::Or(l))
 }
 
-fn collect_or(f: ParserFormula, l: &mut Vec<CnfFormula>, ri: &mut RenamingInfo) {
+fn collect_or(
+    f: ParserFormula,
+    l: &mut Vec<CnfFormula>,
+    ri: &mut RenamingInfo,
+) -> Result<(), String> {
     match f {
         ParserFormula::Or(p, q) => {
-            collect_or(*p, l, ri);
+            collect_or(*p, l, ri)?;
             collect_or(*q, l, ri)
         }
-        _ => l.push(transform_ast(f, ri)),
+        _ => {
+            l.push(transform_ast(f, ri)?);
+            Ok(())
+        }
     }
 }
 
-fn transform_literal(s: String, args: Vec<ParserTerm>, ri: &mut RenamingInfo) -> CnfFormula {
+fn transform_literal(
+    s: String,
+    args: Vec<ParserTerm>,
+    ri: &mut RenamingInfo,
+) -> Result<CnfFormula, String> {
+    let new_args = args
+        .into_iter()
+        .map(|t| transform_term(t, ri))
+        .collect::<Result<Vec<_>, _>>()?;
+
     if s == "=" {
-        assert_eq!(args.len(), 2);
-        CnfFormula::Predicate(0, args.into_iter().map(|t| transform_term(t, ri)).collect())
-    } else if s == "$true" {
-        CnfFormula::True
-    } else if s == "$false" {
-        CnfFormula::False
+        assert_eq!(new_args.len(), 2);
+        Ok(CnfFormula::Predicate(0, new_args))
+    } else if s == "$distinct" {
+        Ok(expand_distinct(new_args))
     } else {
-        let id = ri.get_function_id(s, args.len(), true);
-        CnfFormula::Predicate(
-            id,
-            args.into_iter().map(|t| transform_term(t, ri)).collect(),
+        let id = if ri.has_arithmetic() && is_arithmetic_predicate(&s, new_args.len()) {
+            let arg_sort = term_sort(&new_args[0], ri);
+            ri.get_arithmetic_function_id(s, new_args.len(), arg_sort, BOOLEAN_SORT)
+        } else if s.starts_with('$') {
+            return Err(unknown_symbol_error(&s, new_args.len(), ri));
+        } else {
+            ri.get_function_id(s, new_args.len(), true)
+        };
+        Ok(CnfFormula::Predicate(id, new_args))
+    }
+}
+
+/// Expands `$distinct(t1, ..., tn)` into the disequalities `ti != tj` for all i < j.
+fn expand_distinct(args: Vec<CnfTerm>) -> CnfFormula {
+    let mut l = Vec::<CnfFormula>::new();
+    for (i, t1) in args.iter().enumerate() {
+        for t2 in &args[i + 1..] {
+            let eq = CnfFormula::Predicate(0, vec![t1.clone(), t2.clone()]);
+            l.push(CnfFormula::Not(Box::new(eq)));
+        }
+    }
+
+    match l.len() {
+        0 => CnfFormula::True,
+        1 => l.pop().expect("The list should not be empty"),
+        _ => CnfFormula::And(l),
+    }
+}
+
+fn unknown_symbol_error(s: &str, arity: usize, ri: &RenamingInfo) -> String {
+    if !ri.has_arithmetic()
+        && (is_arithmetic_function(s, arity) || is_arithmetic_predicate(s, arity))
+    {
+        format!(
+            "The arithmetic symbol {} is only supported in tff problems",
+            s
         )
+    } else {
+        format!("Unknown defined symbol {} with arity {}", s, arity)
     }
 }
 
-fn transform_term(t: ParserTerm, ri: &mut RenamingInfo) -> CnfTerm {
+fn transform_term(t: ParserTerm, ri: &mut RenamingInfo) -> Result<CnfTerm, String> {
     match t {
-        ParserTerm::Variable(s) => CnfTerm::Variable(ri.get_variable_id(s)),
+        ParserTerm::Variable(s) => Ok(CnfTerm::Variable(ri.get_variable_id(s))),
         ParserTerm::Function(s, args) => {
-            let id = ri.get_function_id(s, args.len(), false);
-            CnfTerm::Function(
-                id,
-                args.into_iter().map(|t| transform_term(t, ri)).collect(),
-            )
+            let new_args = args
+                .into_iter()
+                .map(|t| transform_term(t, ri))
+                .collect::<Result<Vec<_>, _>>()?;
+            let id = match number_sort(&s) {
+                Some(sort) if ri.has_arithmetic() && new_args.is_empty() => {
+                    let id = ri.get_function_id(s, 0, false);
+                    ri.set_function_sort(id, sort);
+                    id
+                }
+                _ if ri.has_arithmetic() && is_arithmetic_function(&s, new_args.len()) => {
+                    let arg_sort = term_sort(&new_args[0], ri);
+                    let result_sort = arithmetic_result_sort(&s, arg_sort);
+                    ri.get_arithmetic_function_id(s, new_args.len(), arg_sort, result_sort)
+                }
+                _ if s.starts_with('$') => {
+                    return Err(unknown_symbol_error(&s, new_args.len(), ri));
+                }
+                _ => ri.get_function_id(s, new_args.len(), false),
+            };
+            Ok(CnfTerm::Function(id, new_args))
         }
     }
 }
+
+/// Get the sort of a number from its syntax, or None if the string is not a number.
+fn number_sort(s: &str) -> Option<i64> {
+    if !s.starts_with(|c: char| c.is_ascii_digit() || c == '+' || c == '-') {
+        None
+    } else if s.contains('/') {
+        Some(RAT_SORT)
+    } else if s.contains(['.', 'e', 'E']) {
+        Some(REAL_SORT)
+    } else {
+        Some(INT_SORT)
+    }
+}
+
+/// Checks if the given symbol is one of the arithmetic functions of TPTP.
+fn is_arithmetic_function(s: &str, arity: usize) -> bool {
+    match s {
+        "$uminus" | "$floor" | "$ceiling" | "$truncate" | "$round" | "$to_int" | "$to_rat"
+        | "$to_real" => arity == 1,
+        "$sum" | "$difference" | "$product" | "$quotient" | "$quotient_e" | "$quotient_t"
+        | "$quotient_f" | "$remainder_e" | "$remainder_t" | "$remainder_f" => arity == 2,
+        _ => false,
+    }
+}
+
+/// Checks if the given symbol is one of the arithmetic predicates of TPTP.
+fn is_arithmetic_predicate(s: &str, arity: usize) -> bool {
+    match s {
+        "$is_int" | "$is_rat" => arity == 1,
+        "$less" | "$lesseq" | "$greater" | "$greatereq" => arity == 2,
+        _ => false,
+    }
+}
+
+/// Get the sort of the result of an arithmetic function, given the sort of its arguments.
+fn arithmetic_result_sort(s: &str, arg_sort: i64) -> i64 {
+    match s {
+        "$to_int" => INT_SORT,
+        "$to_rat" => RAT_SORT,
+        "$to_real" => REAL_SORT,
+        _ => arg_sort,
+    }
+}
+
+/// Get the sort of an already transformed term.
+fn term_sort(t: &CnfTerm, ri: &RenamingInfo) -> i64 {
+    match *t {
+        CnfTerm::Variable(id) => ri.get_variable_sort(id),
+        CnfTerm::Function(id, _) => ri.get_function_sort(id),
+    }
+}
+
 fn transform_quantifier(
     s: String,
+    sort: &str,
     p: ParserFormula,
     ri: &mut RenamingInfo,
     forall_quantifier: bool,
-) -> CnfFormula {
-    let id = ri.get_variable_id(s);
-    let new_p = Box::new(transform_ast(p, ri));
+) -> Result<CnfFormula, String> {
+    check_sort(sort)?;
+    let sort_id = ri.get_sort_id(sort);
+    let id = ri.bind_variable(s, sort_id);
+    let new_p = transform_ast(p, ri);
+    ri.unbind_variable();
+    let new_p = Box::new(new_p?);
 
     if forall_quantifier {
-        CnfFormula::Forall(id, new_p)
+        Ok(CnfFormula::Forall(id, new_p))
     } else {
-        CnfFormula::Exists(id, new_p)
+        Ok(CnfFormula::Exists(id, new_p))
     }
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::{expand_distinct, number_sort, tptp_str_to_cnf_ast};
+    use crate::cnf::ast::Formula;
+    use crate::cnf::ast::Term;
+    use crate::cnf::renaming_info::{INT_SORT, RAT_SORT, REAL_SORT};
+    use crate::tptp_parser::include_resolver::DenyIncludes;
+    use crate::tptp_parser::parse_error::TptpParseError;
+
+    #[test]
+    fn number_sort_1() {
+        assert_eq!(number_sort("12"), Some(INT_SORT));
+        assert_eq!(number_sort("-12"), Some(INT_SORT));
+        assert_eq!(number_sort("+123/456"), Some(RAT_SORT));
+        assert_eq!(number_sort("1.5e-3"), Some(REAL_SORT));
+        assert_eq!(number_sort("a12"), None);
+    }
+
+    #[test]
+    fn expand_distinct_1() {
+        let a = Term::Function(1, Vec::new());
+        let b = Term::Function(2, Vec::new());
+        let c = Term::Function(3, Vec::new());
+        let neq = |x: &Term, y: &Term| {
+            Formula::Not(Box::new(Formula::Predicate(0, vec![x.clone(), y.clone()])))
+        };
+
+        assert_eq!(expand_distinct(vec![a.clone()]), Formula::True);
+        assert_eq!(expand_distinct(vec![a.clone(), b.clone()]), neq(&a, &b));
+        assert_eq!(
+            expand_distinct(vec![a.clone(), b.clone(), c.clone()]),
+            Formula::And(vec![neq(&a, &b), neq(&a, &c), neq(&b, &c)])
+        );
+    }
+
+    #[test]
+    fn transform_true_false() {
+        let (axioms, _, _) =
+            tptp_str_to_cnf_ast("fof(a, axiom, $true => ~$false).", &DenyIncludes).unwrap();
+        let f = Formula::Implies(
+            Box::new(Formula::True),
+            Box::new(Formula::Not(Box::new(Formula::False))),
+        );
+
+        assert_eq!(axioms, vec![(f, 0)]);
+    }
+
+    #[test]
+    fn transform_unknown_defined_symbol() {
+        let res1 = tptp_str_to_cnf_ast("fof(a, axiom, p($foo)).", &DenyIncludes);
+        let res2 = tptp_str_to_cnf_ast("cnf(a, axiom, $bar(a, b)).", &DenyIncludes);
+        let res3 = tptp_str_to_cnf_ast("fof(a, axiom, $less(1, 2)).", &DenyIncludes);
+        let res4 = tptp_str_to_cnf_ast("tff(a, type, f: $foo > $i).", &DenyIncludes);
+
+        match res1 {
+            Err(TptpParseError::Input(s)) => assert!(s.contains("$foo")),
+            _ => panic!("$foo should have been rejected"),
+        }
+        match res2 {
+            Err(TptpParseError::Input(s)) => assert!(s.contains("$bar")),
+            _ => panic!("$bar should have been rejected"),
+        }
+        match res3 {
+            Err(TptpParseError::Input(s)) => assert!(s.contains("tff")),
+            _ => panic!("$less should have been rejected outside of tff"),
+        }
+        match res4 {
+            Err(TptpParseError::Input(s)) => assert!(s.contains("$foo")),
+            _ => panic!("$foo should have been rejected"),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::tptp_parser::ast::{Annotations, GeneralTerm};
+
+/// Information about where an input formula came from.
+/// Used for referring to the input formulas in proofs and error messages.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FormulaOrigin {
+    name: String,
+    role: String,
+    file: Option<String>,
+    source: Option<GeneralTerm>,
+    useful_info: Vec<GeneralTerm>,
+}
+
+impl FormulaOrigin {
+    /// Creates a new origin for the formula with the given name and role.
+    /// The file is the one the formula was read from, if any.
+    pub fn new(
+        name: String,
+        role: String,
+        file: Option<String>,
+        annotations: Option<Annotations>,
+    ) -> FormulaOrigin {
+        let (source, useful_info) = match annotations {
+            Some((source, useful_info)) => (Some(source), useful_info.unwrap_or_default()),
+            None => (None, Vec::new()),
+        };
+        FormulaOrigin {
+            name,
+            role,
+            file,
+            source,
+            useful_info,
+        }
+    }
+
+    /// Get the name of the formula.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the role of the formula.
+    pub fn get_role(&self) -> &str {
+        &self.role
+    }
+
+    /// Get the file the formula was read from, if any.
+    pub fn get_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Get the source annotation of the formula, if it has one.
+    pub fn get_source(&self) -> Option<&GeneralTerm> {
+        self.source.as_ref()
+    }
+
+    /// Get the useful info annotated to the formula.
+    pub fn get_useful_info(&self) -> &[GeneralTerm] {
+        &self.useful_info
+    }
+
+    /// Checks if the formula is a conjecture or a negated conjecture.
+    pub fn is_conjecture(&self) -> bool {
+        self.role == "conjecture" || self.role == "negated_conjecture"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FormulaOrigin;
+    use crate::tptp_parser::ast::GeneralTerm;
+
+    #[test]
+    fn new_1() {
+        let source = GeneralTerm::Data("unknown".to_owned());
+        let info = GeneralTerm::Data("thm".to_owned());
+        let o1 = FormulaOrigin::new(
+            "c".to_owned(),
+            "conjecture".to_owned(),
+            Some("a.p".to_owned()),
+            Some((source.clone(), Some(vec![info.clone()]))),
+        );
+        let o2 = FormulaOrigin::new("a".to_owned(), "axiom".to_owned(), None, None);
+
+        assert_eq!(o1.get_name(), "c");
+        assert_eq!(o1.get_file(), Some("a.p"));
+        assert_eq!(o1.get_source(), Some(&source));
+        assert_eq!(o1.get_useful_info(), &[info]);
+        assert!(o1.is_conjecture());
+        assert_eq!(o2.get_file(), None);
+        assert_eq!(o2.get_source(), None);
+        assert!(o2.get_useful_info().is_empty());
+        assert!(!o2.is_conjecture());
+    }
+}
@@ -27,6 +27,9 @@ pub mod ast;
 /// Contains stuff dealing with converting predicate and term names to a more suitable format.
 pub mod renaming_info;
 
+/// Contains the information about where each input formula came from.
+pub mod formula_origin;
+
 /// Contains functions for turning the TPTP parser AST into the CNF AST.
 pub mod ast_transformer;
 
@@ -125,10 +125,7 @@ fn move_nots_inward_not(f: Formula) -> Formula {
 fn is_in_nnf(f: &Formula) -> bool {
     match *f {
         Formula::Predicate(_, _) => true,
-        Formula::Not(ref p) => match **p {
-            Formula::Predicate(_, _) => true,
-            _ => false,
-        },
+        Formula::Not(ref p) => matches!(**p, Formula::Predicate(_, _)),
         Formula::And(ref l) | Formula::Or(ref l) => l.iter().all(is_in_nnf),
         Formula::Forall(_, ref p) | Formula::Exists(_, ref p) => is_in_nnf(p),
         _ => false,
@@ -15,6 +15,7 @@
 //
 
 use crate::cnf::ast::{Formula, Term};
+use crate::cnf::formula_origin::FormulaOrigin;
 use crate::cnf::free_variables::free_variables;
 use std::collections::HashMap;
 
@@ -67,15 +68,43 @@ impl Definition {
     }
 }
 
+/// The ID of the sort of individuals, `$i`. Everything untyped belongs to this sort.
+pub const INDIVIDUAL_SORT: i64 = 0;
+
+/// The ID of the sort of booleans, `$o`. Only predicates belong to this sort.
+pub const BOOLEAN_SORT: i64 = 1;
+
+/// The ID of the sort of integers, `$int`.
+pub const INT_SORT: i64 = 2;
+
+/// The ID of the sort of rationals, `$rat`.
+pub const RAT_SORT: i64 = 3;
+
+/// The ID of the sort of reals, `$real`.
+pub const REAL_SORT: i64 = 4;
+
+/// The names of the sorts which exist without a declaration, in the order of their IDs.
+pub const BUILTIN_SORTS: [&str; 5] = ["$i", "$o", "$int", "$rat", "$real"];
+
 /// Mappings from literals, terms and variables to IDs.
 /// Variables get a negative ID, functions get a positive ID while equality gets 0.
+/// Sorts get a non-negative ID of their own.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct RenamingInfo {
     fun_map: HashMap<(String, usize, bool), i64>,
-    var_map: HashMap<String, i64>,
+    arith_map: HashMap<(String, usize, i64), i64>,
+    var_map: HashMap<(String, i64), i64>,
+    fun_names: HashMap<i64, String>,
+    fun_sorts: HashMap<i64, i64>,
+    var_sorts: HashMap<i64, i64>,
+    sort_map: HashMap<String, i64>,
+    sort_names: Vec<String>,
+    bound_vars: Vec<(String, i64)>,
+    arithmetic: bool,
     fun_cnt: i64,
     var_cnt: i64,
     defs: Vec<Definition>,
+    origins: Vec<FormulaOrigin>,
 }
 
 impl RenamingInfo {
@@ -84,24 +113,62 @@ impl RenamingInfo {
         RenamingInfo {
             var_map: HashMap::new(),
             fun_map: HashMap::new(),
+            arith_map: HashMap::new(),
+            fun_names: HashMap::new(),
+            fun_sorts: HashMap::new(),
+            var_sorts: HashMap::new(),
+            sort_map: BUILTIN_SORTS
+                .iter()
+                .enumerate()
+                .map(|(i, &s)| (s.to_owned(), i as i64))
+                .collect(),
+            sort_names: BUILTIN_SORTS.iter().map(|&s| s.to_owned()).collect(),
+            bound_vars: Vec::new(),
+            arithmetic: false,
             var_cnt: 0,
             fun_cnt: 0,
             defs: Vec::new(),
+            origins: Vec::new(),
         }
     }
 
-    /// If there already is an ID for the given variable name, return that.
-    /// Otherwise create a new ID for it.
+    /// Returns the ID of the variable with the given name.
+    /// If a quantifier currently binds the name the ID of the bound variable is returned.
+    /// Otherwise the variable is a free variable of the sort `$i`.
     pub fn get_variable_id(&mut self, s: String) -> i64 {
-        if let Some(&id) = self.var_map.get(&s) {
+        if let Some(&(_, id)) = self.bound_vars.iter().rev().find(|x| x.0 == s) {
             id
         } else {
-            self.var_cnt -= 1;
-            self.var_map.insert(s, self.var_cnt);
-            self.var_cnt
+            self.get_sorted_variable_id(s, INDIVIDUAL_SORT)
         }
     }
 
+    /// If there already is an ID for the given variable name and sort, return that.
+    /// Otherwise create a new ID for it.
+    pub fn get_sorted_variable_id(&mut self, s: String, sort: i64) -> i64 {
+        if let Some(&id) = self.var_map.get(&(s.clone(), sort)) {
+            id
+        } else {
+            let id = self.create_new_variable_id();
+            self.set_variable_sort(id, sort);
+            self.var_map.insert((s, sort), id);
+            id
+        }
+    }
+
+    /// Binds a variable name to a variable of the given sort until `unbind_variable` is called.
+    /// Returns the ID of the bound variable.
+    pub fn bind_variable(&mut self, s: String, sort: i64) -> i64 {
+        let id = self.get_sorted_variable_id(s.clone(), sort);
+        self.bound_vars.push((s, id));
+        id
+    }
+
+    /// Removes the innermost binding created by `bind_variable`.
+    pub fn unbind_variable(&mut self) {
+        self.bound_vars.pop();
+    }
+
     /// If there already is an ID for the given function name and arity, return that.
     /// Otherwise create a new ID for it.
     pub fn get_function_id(&mut self, s: String, arity: usize, predicate: bool) -> i64 {
@@ -109,14 +176,147 @@ impl RenamingInfo {
             id
         } else {
             self.fun_cnt += 1;
+            self.fun_names.insert(self.fun_cnt, s.clone());
             self.fun_map.insert((s, arity, predicate), self.fun_cnt);
             self.fun_cnt
         }
     }
 
+    /// Get the ID of an arithmetic function or predicate for arguments of the given sort.
+    /// Arithmetic symbols are overloaded, so each argument sort gets its own ID.
+    /// A result sort of `$o` means that the symbol is a predicate.
+    pub fn get_arithmetic_function_id(
+        &mut self,
+        s: String,
+        arity: usize,
+        arg_sort: i64,
+        result_sort: i64,
+    ) -> i64 {
+        if let Some(&id) = self.arith_map.get(&(s.clone(), arity, arg_sort)) {
+            id
+        } else {
+            self.fun_cnt += 1;
+            self.fun_names.insert(self.fun_cnt, s.clone());
+            self.arith_map.insert((s, arity, arg_sort), self.fun_cnt);
+            if result_sort != BOOLEAN_SORT {
+                self.set_function_sort(self.fun_cnt, result_sort);
+            }
+            self.fun_cnt
+        }
+    }
+
+    /// Makes numbers and arithmetic symbols interpreted.
+    /// This is only done for typed problems, as otherwise they are plain constants and functions.
+    pub fn enable_arithmetic(&mut self) {
+        self.arithmetic = true;
+    }
+
+    /// Checks if numbers and arithmetic symbols are interpreted.
+    pub fn has_arithmetic(&self) -> bool {
+        self.arithmetic
+    }
+
+    /// Declares the type of a function or a predicate.
+    /// A result sort of `$o` means that the symbol is a predicate.
+    /// Returns the ID of the symbol.
+    pub fn declare_function(&mut self, s: String, arg_sorts: &[String], result_sort: &str) -> i64 {
+        let sort = self.get_sort_id(result_sort);
+        if sort == BOOLEAN_SORT {
+            self.get_function_id(s, arg_sorts.len(), true)
+        } else {
+            let id = self.get_function_id(s, arg_sorts.len(), false);
+            self.set_function_sort(id, sort);
+            id
+        }
+    }
+
+    /// If there already is an ID for the given sort name, return that.
+    /// Otherwise create a new ID for it.
+    pub fn get_sort_id(&mut self, s: &str) -> i64 {
+        if let Some(&id) = self.sort_map.get(s) {
+            id
+        } else {
+            let id = self.sort_names.len() as i64;
+            self.sort_map.insert(s.to_owned(), id);
+            self.sort_names.push(s.to_owned());
+            id
+        }
+    }
+
+    /// Get the name of the sort with the given ID.
+    pub fn get_sort_name(&self, sort: i64) -> Option<&str> {
+        self.sort_names.get(sort as usize).map(|s| s.as_str())
+    }
+
+    /// Sets the result sort of the function with the given ID.
+    pub fn set_function_sort(&mut self, id: i64, sort: i64) {
+        if sort == INDIVIDUAL_SORT {
+            self.fun_sorts.remove(&id);
+        } else {
+            self.fun_sorts.insert(id, sort);
+        }
+    }
+
+    /// Get the result sort of the function with the given ID.
+    /// Functions without a declared type are of the sort `$i`.
+    pub fn get_function_sort(&self, id: i64) -> i64 {
+        self.fun_sorts.get(&id).cloned().unwrap_or(INDIVIDUAL_SORT)
+    }
+
+    /// Sets the sort of the variable with the given ID.
+    pub fn set_variable_sort(&mut self, id: i64, sort: i64) {
+        if sort == INDIVIDUAL_SORT {
+            self.var_sorts.remove(&id);
+        } else {
+            self.var_sorts.insert(id, sort);
+        }
+    }
+
+    /// Get the sort of the variable with the given ID.
+    pub fn get_variable_sort(&self, id: i64) -> i64 {
+        self.var_sorts.get(&id).cloned().unwrap_or(INDIVIDUAL_SORT)
+    }
+
+    /// Checks if some function or variable has the given sort.
+    pub fn uses_sort(&self, sort: i64) -> bool {
+        self.fun_sorts.values().any(|&x| x == sort) || self.var_sorts.values().any(|&x| x == sort)
+    }
+
+    /// Get the original name of the function or predicate with the given ID.
+    /// Skolem functions and definitions get generated names.
+    pub fn get_function_name(&self, id: i64) -> Option<&str> {
+        self.fun_names.get(&id).map(|s| s.as_str())
+    }
+
+    /// Iterates over the IDs and names of all functions and predicates.
+    pub fn iter_function_names(&self) -> impl Iterator<Item = (i64, &str)> {
+        self.fun_names.iter().map(|(&id, s)| (id, s.as_str()))
+    }
+
+    /// Get the largest function ID created so far.
+    pub fn get_newest_function_id(&self) -> i64 {
+        self.fun_cnt
+    }
+
+    /// Names a function which was created during the proof search, such as a computed number.
+    /// The ID should be larger than any ID created so far.
+    pub fn add_function_name(&mut self, id: i64, s: String) {
+        assert!(id > self.fun_cnt);
+        self.fun_cnt = id;
+        self.fun_names.insert(id, s);
+    }
+
+    /// Checks if the function with the given ID is a distinct object, i.e. a "double quoted" constant.
+    pub fn is_distinct_object(&self, id: i64) -> bool {
+        self.get_function_name(id)
+            .is_some_and(|s| s.starts_with('"'))
+    }
+
     /// Creates a new skolem function ID.
     pub fn create_new_skolem_function_id(&mut self) -> i64 {
         self.fun_cnt += 1;
+        self.fun_names
+            .insert(self.fun_cnt, format!("esk{}", self.fun_cnt));
         self.fun_cnt
     }
 
@@ -131,6 +331,18 @@ impl RenamingInfo {
         self.var_cnt
     }
 
+    /// Records the origin of an input formula.
+    /// Returns the ID used for referring to the origin.
+    pub fn add_formula_origin(&mut self, origin: FormulaOrigin) -> usize {
+        self.origins.push(origin);
+        self.origins.len() - 1
+    }
+
+    /// Get the origin of an input formula with the given ID.
+    pub fn get_formula_origin(&self, id: usize) -> &FormulaOrigin {
+        &self.origins[id]
+    }
+
     /// Get a definition corresponding to a given formula, if it exists.
     pub fn get_definition(&mut self, f: &Formula, polarity: Polarity) -> Option<Formula> {
         match self.defs.iter().position(|x| x.f == *f) {
@@ -150,12 +362,14 @@ impl RenamingInfo {
     /// Creates a new definition for a given formula and polarity.
     pub fn create_new_definition(&mut self, f: &Formula, polarity: Polarity) -> Formula {
         self.fun_cnt += 1;
+        self.fun_names
+            .insert(self.fun_cnt, format!("epred{}", self.fun_cnt));
         let free_vars = free_variables(f).into_iter().map(Term::Variable).collect();
         let pred = Formula::Predicate(self.fun_cnt, free_vars);
         self.defs.push(Definition {
             f: f.clone(),
             predicate: pred.clone(),
-            polarity: polarity,
+            polarity,
         });
         pred
     }
@@ -177,7 +391,7 @@ impl Default for RenamingInfo {
 
 #[cfg(test)]
 mod test {
-    use super::RenamingInfo;
+    use super::{RenamingInfo, INDIVIDUAL_SORT};
 
     #[test]
     fn get_variable_id_1() {
@@ -206,4 +420,48 @@ mod test {
         assert_eq!(id4, 3);
         assert_eq!(id5, 4);
     }
+
+    #[test]
+    fn get_sorted_variable_id_1() {
+        let mut ri = RenamingInfo::new();
+        let a = ri.get_sort_id("a");
+        let id1 = ri.get_sorted_variable_id("X".to_owned(), a);
+        let id2 = ri.get_variable_id("X".to_owned());
+        let id3 = ri.bind_variable("X".to_owned(), a);
+        let id4 = ri.get_variable_id("X".to_owned());
+        ri.unbind_variable();
+        let id5 = ri.get_variable_id("X".to_owned());
+
+        assert_ne!(id1, id2);
+        assert_eq!(id1, id3);
+        assert_eq!(id3, id4);
+        assert_eq!(id2, id5);
+        assert_eq!(ri.get_variable_sort(id1), a);
+        assert_eq!(ri.get_variable_sort(id2), INDIVIDUAL_SORT);
+    }
+
+    #[test]
+    fn declare_function_1() {
+        let mut ri = RenamingInfo::new();
+        let a = ri.get_sort_id("a");
+        let id1 = ri.declare_function("f".to_owned(), &["$i".to_owned()], "a");
+        let id2 = ri.declare_function("p".to_owned(), &["a".to_owned()], "$o");
+
+        assert_eq!(ri.get_function_id("f".to_owned(), 1, false), id1);
+        assert_eq!(ri.get_function_id("p".to_owned(), 1, true), id2);
+        assert_eq!(ri.get_function_sort(id1), a);
+        assert_eq!(ri.get_sort_name(a), Some("a"));
+        assert_eq!(ri.get_sort_id("$int"), 2);
+    }
+
+    #[test]
+    fn get_function_name_1() {
+        let mut ri = RenamingInfo::new();
+        let id1 = ri.get_function_id("f".to_owned(), 2, false);
+        let id2 = ri.create_new_skolem_function_id();
+
+        assert_eq!(ri.get_function_name(id1), Some("f"));
+        assert_eq!(ri.get_function_name(id2), Some("esk2"));
+        assert_eq!(ri.get_function_name(3), None);
+    }
 }
@@ -198,7 +198,7 @@ fn contains_true_or_false(f: &Formula) -> bool {
     match *f {
         Formula::True | Formula::False => true,
         Formula::Predicate(_, _) => false,
-        Formula::And(ref l) | Formula::Or(ref l) => l.iter().any(|x| contains_true_or_false(x)),
+        Formula::And(ref l) | Formula::Or(ref l) => l.iter().any(contains_true_or_false),
         Formula::Implies(ref p, ref q) | Formula::Equivalent(ref p, ref q) => {
             contains_true_or_false(p) || contains_true_or_false(q)
         }
@@ -61,10 +61,7 @@ fn is_literal(f: &Formula) -> bool {
 
 /// Checks whether a formula is a literal.
 fn is_atomic(f: &Formula) -> bool {
-    match *f {
-        Formula::Predicate(_, _) => true,
-        _ => false,
-    }
+    matches!(*f, Formula::Predicate(_, _))
 }
 
 #[cfg(test)]
@@ -40,6 +40,7 @@ fn skolemize1(f: Formula, ri: &mut RenamingInfo) -> Formula {
 
 fn skolemize_exists(id: i64, f: Formula, ri: &mut RenamingInfo) -> Formula {
     let skolem_f_id = ri.create_new_skolem_function_id();
+    ri.set_function_sort(skolem_f_id, ri.get_variable_sort(id));
     let mut vars = free_variables(&f);
     vars.remove(&id);
     let sf = Term::Function(skolem_f_id, vars.into_iter().map(Term::Variable).collect());
@@ -36,6 +36,7 @@ fn rename_quantifier(
     universal_quantifier: bool,
 ) -> Formula {
     let new_id = ri.create_new_variable_id();
+    ri.set_variable_sort(new_id, ri.get_variable_sort(id));
     let renamed_p = rename(rename_variable(p, id, new_id), ri);
 
     if universal_quantifier {
@@ -14,98 +14,125 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
-//! Serkr is an automated theorem prover for first order logic.
-
-// Some lints which are pretty useful.
-/*
-#![deny(fat_ptr_transmutes,
-        unused_extern_crates,
-        variant_size_differences,
-        missing_docs,
-        missing_debug_implementations,
-        missing_copy_implementations,
-        trivial_casts,
-        trivial_numeric_casts,
-        unsafe_code,
-        unused_import_braces,
-        unused_qualifications)]
-*/
-// Might as well make the warnings errors.
-//#![deny(warnings)]
-
-/*
-// Clippy lints turned to the max.
-#![cfg_attr(feature="clippy", feature(plugin))]
-#![cfg_attr(feature="clippy", plugin(clippy))]
-#![cfg_attr(feature="clippy", deny(clippy))]
-#![cfg_attr(feature="clippy", deny(clippy_pedantic))]
-#![cfg_attr(feature="clippy", allow(similar_names,
-                                    stutter,
-                                    missing_docs_in_private_items))]
-*/
+//! The command line interface of Serkr.
 
 #[macro_use]
 extern crate clap;
-extern crate fnv;
-extern crate num;
-
-#[macro_use]
-pub mod utils;
-pub mod cnf;
-pub mod prover;
-pub mod tptp_parser;
-
 #[macro_use]
-extern crate lalrpop_util;
+extern crate serkr;
 
-use crate::prover::proof_result::ProofResult;
-use crate::prover::proof_statistics::*;
-use crate::utils::stopwatch::Stopwatch;
+use serkr::prover::axiom_selection::AxiomSelection;
+use serkr::prover::builder::ProverOutput;
+use serkr::prover::clause_selection::heuristic::parse_heuristics;
+use serkr::prover::inference::literal_selection::LiteralSelection;
+use serkr::prover::proof_statistics::ProofStatistics;
+use serkr::prover::schedule::Schedule;
+use serkr::tptp_parser::include_resolver::FileSystemResolver;
+use serkr::utils::stopwatch::Stopwatch;
+use serkr::Prover;
 
 #[cfg_attr(feature = "clippy", allow(print_stdout))]
-fn print_proof_result(proof_result: &ProofResult, input_file: &str) {
+fn print_proof_result(
+    output: &ProverOutput,
+    input_file: &str,
+    print_saturation: bool,
+    print_unsat_core: bool,
+) {
+    let proof_result = output.get_result();
     println_szs!(
         "SZS status {} for {}",
         proof_result.display_type(),
         input_file
     );
-    if proof_result.is_successful() {
+    if let Some(message) = proof_result.get_error_message() {
+        for line in message.lines() {
+            println_szs!("{}", line);
+        }
+    }
+    let proof = output.get_proof().unwrap_or_default();
+    let unsat_core = output.get_unsat_core().unwrap_or_default();
+    if proof_result.is_refutation() && print_unsat_core && !unsat_core.is_empty() {
+        println_szs!("SZS output start ListOfFormulae for {}", input_file);
+        for name in unsat_core {
+            println!("{}", name);
+        }
+        println_szs!("SZS output end ListOfFormulae for {}", input_file);
+    } else if proof_result.is_refutation() && !proof.is_empty() {
+        println_szs!("SZS output start CNFRefutation for {}", input_file);
+        for line in proof {
+            println!("{}", line);
+        }
+        println_szs!("SZS output end CNFRefutation for {}", input_file);
+    } else if proof_result.is_saturation() && print_saturation {
+        println_szs!("SZS output start Saturation for {}", input_file);
+        for line in proof {
+            println!("{}", line);
+        }
+        println_szs!("SZS output end Saturation for {}", input_file);
+    } else if proof_result.is_successful() {
         println_szs!(
             "SZS output None for {} : Proof output is not yet supported",
             input_file
         );
     }
-    println!("");
+    println!();
 }
 
 #[cfg_attr(feature = "clippy", allow(print_stdout))]
-fn print_statistics(sw: &Stopwatch) {
+fn print_statistics(stats: &ProofStatistics, sw: &Stopwatch) {
     println_szs!("Time elapsed (in ms): {}", sw.elapsed_ms());
 
-    println_szs!("Initial clauses: {}", get_initial_clauses());
-    println_szs!("Analyzed clauses: {}", get_iteration_count());
-    println_szs!("  Trivial: {}", get_trivial_count());
-    println_szs!("  Forward subsumed: {}", get_forward_subsumed_count());
-    println_szs!("  Nonredundant: {}", get_nonredundant_analyzed_count());
+    println_szs!("Initial clauses: {}", stats.get_initial_clauses());
+    println_szs!("Analyzed clauses: {}", stats.get_iteration_count());
+    println_szs!("  Trivial: {}", stats.get_trivial_count());
+    println_szs!("  Forward subsumed: {}", stats.get_forward_subsumed_count());
+    println_szs!(
+        "  Nonredundant: {}",
+        stats.get_nonredundant_analyzed_count()
+    );
 
-    println_szs!("Backward subsumptions: {}", get_backward_subsumed_count());
+    println_szs!(
+        "Backward subsumptions: {}",
+        stats.get_backward_subsumed_count()
+    );
+    println_szs!(
+        "Backward rewrites: {}",
+        stats.get_backward_rewritten_count()
+    );
 
-    println_szs!("Inferred clauses: {}", get_inferred_count());
-    println_szs!("  Superposition: {}", get_superposition_inferred_count());
+    println_szs!("Inferred clauses: {}", stats.get_inferred_count());
+    println_szs!(
+        "  Superposition: {}",
+        stats.get_superposition_inferred_count()
+    );
     println_szs!(
         "  Equality factoring: {}",
-        get_equality_factoring_inferred_count()
+        stats.get_equality_factoring_inferred_count()
     );
     println_szs!(
         "  Equality resolution: {}",
-        get_equality_resolution_inferred_count()
+        stats.get_equality_resolution_inferred_count()
     );
     println_szs!(
         "Nontrivial inferred clauses: {}",
-        get_nontrivial_inferred_count()
+        stats.get_nontrivial_inferred_count()
     );
 }
 
+/// The options which are set by the strategies of a schedule.
+const STRATEGY_OPTIONS: [&str; 10] = [
+    "lpo",
+    "kbo",
+    "formula-renaming",
+    "heuristics",
+    "literal-selection",
+    "set-of-support",
+    "no-sine",
+    "sine-tolerance",
+    "sine-depth",
+    "sine-min-axioms",
+];
+
 fn main() {
     let matches = clap::App::new("Serkr")
         .version(crate_version!())
@@ -119,6 +146,13 @@ fn main() {
                 .long("time-limit")
                 .value_name("arg"),
         )
+        .arg(
+            clap::Arg::with_name("memory-limit")
+                .help("Memory limit for storing clauses in megabytes (default=unlimited)")
+                .short("m")
+                .long("memory-limit")
+                .value_name("arg"),
+        )
         .arg(
             clap::Arg::with_name("lpo")
                 .help("Use LPO as the term ordering")
@@ -144,38 +178,190 @@ fn main() {
                 .long("formula-renaming")
                 .value_name("arg"),
         )
+        .arg(
+            clap::Arg::with_name("heuristics")
+                .help(
+                    "The heuristics for picking the given clause, each with the amount of \
+                                      times it is used in a row. The heuristics are size(f,v), \
+                                      goal(f,v), age, literals, depth and conjecture \
+                                      (default=size(2,1):4,age:1)",
+                )
+                .long("heuristics")
+                .value_name("arg"),
+        )
+        .arg(
+            clap::Arg::with_name("literal-selection")
+                .help("How negative literals are selected for inferences (default=none)")
+                .long("literal-selection")
+                .value_name("arg")
+                .possible_values(&["none", "max-weight", "all-negative", "complex"]),
+        )
+        .arg(
+            clap::Arg::with_name("set-of-support")
+                .help(
+                    "Only use clauses which don't come from the negated conjecture in \
+                                      inferences with clauses which do",
+                )
+                .long("set-of-support"),
+        )
+        .arg(
+            clap::Arg::with_name("tptp-root")
+                .help(
+                    "The TPTP root directory for resolving includes. Defaults to the TPTP \
+                                      environment variable.",
+                )
+                .long("tptp-root")
+                .value_name("dir"),
+        )
+        .arg(
+            clap::Arg::with_name("print-saturation")
+                .help(
+                    "Print the saturated clause set if the problem is found to be \
+                                      satisfiable or countersatisfiable",
+                )
+                .long("print-saturation"),
+        )
+        .arg(
+            clap::Arg::with_name("no-sine")
+                .help(
+                    "Use all axioms instead of selecting the ones relevant to the conjectures \
+                                      with SInE",
+                )
+                .long("no-sine"),
+        )
+        .arg(
+            clap::Arg::with_name("sine-tolerance")
+                .help(
+                    "How much more common than the rarest symbol of an axiom a symbol can be \
+                                      and still make SInE select the axiom (default=1.5)",
+                )
+                .long("sine-tolerance")
+                .value_name("arg")
+                .conflicts_with("no-sine"),
+        )
+        .arg(
+            clap::Arg::with_name("sine-depth")
+                .help(
+                    "How many steps SInE follows symbols from the conjectures \
+                                      (default=unlimited)",
+                )
+                .long("sine-depth")
+                .value_name("arg")
+                .conflicts_with("no-sine"),
+        )
+        .arg(
+            clap::Arg::with_name("sine-min-axioms")
+                .help(
+                    "How many axioms a problem needs to have before SInE is used \
+                                      (default=64)",
+                )
+                .long("sine-min-axioms")
+                .value_name("arg")
+                .conflicts_with("no-sine"),
+        )
+        .arg(
+            clap::Arg::with_name("schedule")
+                .help(
+                    "Split the time limit between several strategies of the built-in \
+                                      schedule instead of running a single one",
+                )
+                .long("schedule")
+                .conflicts_with_all(&STRATEGY_OPTIONS),
+        )
+        .arg(
+            clap::Arg::with_name("schedule-file")
+                .help(
+                    "Split the time limit between the strategies in the given file, \
+                                      one strategy per line",
+                )
+                .long("schedule-file")
+                .value_name("file")
+                .conflicts_with_all(&STRATEGY_OPTIONS)
+                .conflicts_with("schedule"),
+        )
+        .arg(
+            clap::Arg::with_name("unsat-core")
+                .help(
+                    "Print the names of the input formulas used in the refutation instead \
+                                      of the refutation itself",
+                )
+                .long("unsat-core"),
+        )
         .get_matches();
 
-    // Hack to get around lifetime issues.
-    let input_file_name = matches
-        .value_of("INPUT")
-        .expect("This should always be OK")
-        .to_owned();
+    let input_file = matches.value_of("INPUT").expect("This should always be OK");
     let time_limit_ms = value_t!(matches, "time-limit", u64).unwrap_or(300) * 1000;
+    let print_saturation = matches.is_present("print-saturation");
+    let print_unsat_core = matches.is_present("unsat-core");
+    let mut prover = Prover::new()
+        .use_lpo(matches.is_present("lpo"))
+        .renaming_limit(value_t!(matches, "formula-renaming", u64).unwrap_or(32))
+        .set_of_support(matches.is_present("set-of-support"))
+        .time_limit_ms(time_limit_ms);
+    let mut resolver = FileSystemResolver::new();
+    if let Some(root) = matches.value_of("tptp-root") {
+        resolver.set_tptp_root(root);
+    }
+    prover = prover.include_resolver(resolver);
+    if !matches.is_present("no-sine") {
+        prover = prover.axiom_selection(AxiomSelection::Sine {
+            tolerance: if matches.is_present("sine-tolerance") {
+                value_t!(matches, "sine-tolerance", f64).unwrap_or_else(|e| e.exit())
+            } else {
+                1.5
+            },
+            depth: if matches.is_present("sine-depth") {
+                Some(value_t!(matches, "sine-depth", usize).unwrap_or_else(|e| e.exit()))
+            } else {
+                None
+            },
+            min_axioms: if matches.is_present("sine-min-axioms") {
+                value_t!(matches, "sine-min-axioms", usize).unwrap_or_else(|e| e.exit())
+            } else {
+                64
+            },
+        });
+    }
+    if let Some(heuristics) = matches.value_of("heuristics") {
+        match parse_heuristics(heuristics).and_then(|heuristics| prover.heuristics(heuristics)) {
+            Ok(new_prover) => prover = new_prover,
+            Err(e) => clap::Error::with_description(&e, clap::ErrorKind::InvalidValue).exit(),
+        }
+    }
+    if let Ok(literal_selection) = value_t!(matches, "literal-selection", LiteralSelection) {
+        prover = prover.literal_selection(literal_selection);
+    }
+    if let Ok(memory_limit_mb) = value_t!(matches, "memory-limit", usize) {
+        prover = prover.memory_limit_mb(memory_limit_mb);
+    }
 
-    // The stack size is a hack to get around the parser/CNF transformer from crashing with very large files.
-    let _ = std::thread::Builder::new()
-        .stack_size(32 * 1024 * 1024)
-        .spawn(move || {
-            let input_file = matches.value_of("INPUT").expect("This should always be OK");
-            let use_lpo = matches.is_present("lpo");
-            let renaming_limit = value_t!(matches, "formula-renaming", u64).unwrap_or(32);
-            prover::proof_search::prove(input_file, use_lpo, renaming_limit)
-        })
-        .expect("Creating a new thread shouldn't fail");
+    let schedule = if let Some(path) = matches.value_of("schedule-file") {
+        match Schedule::from_file(path) {
+            Ok(schedule) => Some(schedule),
+            Err(e) => clap::Error::with_description(&e, clap::ErrorKind::InvalidValue).exit(),
+        }
+    } else if matches.is_present("schedule") {
+        Some(Schedule::default())
+    } else {
+        None
+    };
 
     let mut sw = Stopwatch::new();
-    let resolution = std::time::Duration::from_millis(10);
-
     sw.start();
-    while sw.elapsed_ms() < time_limit_ms && !has_search_finished() {
-        std::thread::sleep(resolution);
-    }
+    let (output, strategy) = match schedule {
+        Some(ref schedule) => {
+            let (output, strategy) = schedule.prove_file(&prover, input_file);
+            (output, Some(strategy.get_name()))
+        }
+        None => (prover.prove_file(input_file), None),
+    };
     sw.stop();
 
-    let proof_result = get_proof_result();
-    print_proof_result(&proof_result, &input_file_name);
-    if !proof_result.is_err() {
-        print_statistics(&sw);
+    print_proof_result(&output, input_file, print_saturation, print_unsat_core);
+    if !output.get_result().is_err() {
+        if let Some(strategy) = strategy {
+            println_szs!("Strategy: {}", strategy);
+        }
+        print_statistics(output.get_statistics(), &sw);
     }
 }
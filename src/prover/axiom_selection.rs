@@ -0,0 +1,209 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::cnf::ast::{Formula, Term};
+use crate::utils::hash_map::HashMap;
+
+/// Decides which axioms are given to the proof search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxiomSelection {
+    /// Use all axioms.
+    All,
+    /// Use the axioms SInE finds relevant to the conjectures.
+    /// An axiom is triggered by a symbol if the symbol occurs in at most `tolerance` times as many
+    /// formulas as the rarest symbol of the axiom.
+    /// The depth limits how many times symbols are followed from the conjectures, `None` means no limit.
+    /// Problems with less than `min_axioms` axioms are small enough to use all of them.
+    Sine {
+        tolerance: f64,
+        depth: Option<usize>,
+        min_axioms: usize,
+    },
+}
+
+/// Collects the function and predicate symbols of a term.
+fn collect_term_symbols(t: &Term, symbols: &mut Vec<i64>) {
+    if let Term::Function(id, ref args) = *t {
+        symbols.push(id);
+        for arg in args {
+            collect_term_symbols(arg, symbols);
+        }
+    }
+}
+
+/// Collects the function and predicate symbols of a formula. Equality doesn't count as a symbol.
+fn collect_symbols(f: &Formula, symbols: &mut Vec<i64>) {
+    match *f {
+        Formula::True | Formula::False => {}
+        Formula::Predicate(id, ref args) => {
+            if id != 0 {
+                symbols.push(id);
+            }
+            for arg in args {
+                collect_term_symbols(arg, symbols);
+            }
+        }
+        Formula::Not(ref p) | Formula::Forall(_, ref p) | Formula::Exists(_, ref p) => {
+            collect_symbols(p, symbols)
+        }
+        Formula::And(ref l) | Formula::Or(ref l) => {
+            for p in l {
+                collect_symbols(p, symbols);
+            }
+        }
+        Formula::Implies(ref p, ref q) | Formula::Equivalent(ref p, ref q) => {
+            collect_symbols(p, symbols);
+            collect_symbols(q, symbols);
+        }
+    }
+}
+
+/// Get the distinct symbols of a formula in ascending order.
+fn symbols(f: &Formula) -> Vec<i64> {
+    let mut symbols = Vec::new();
+    collect_symbols(f, &mut symbols);
+    symbols.sort_unstable();
+    symbols.dedup();
+    symbols
+}
+
+/// Selects the axioms relevant to the goals with the SInE algorithm.
+/// The goals are the (negated) conjectures of the problem.
+/// Axioms without any symbols are always selected, as are all axioms if there are no goals.
+pub fn sine_select<T>(
+    axioms: Vec<(Formula, T)>,
+    goals: &[&Formula],
+    tolerance: f64,
+    depth: Option<usize>,
+) -> Vec<(Formula, T)> {
+    if goals.is_empty() {
+        return axioms;
+    }
+
+    let axiom_symbols = axioms.iter().map(|x| symbols(&x.0)).collect::<Vec<_>>();
+    let goal_symbols = goals.iter().map(|f| symbols(f)).collect::<Vec<_>>();
+
+    // Count the amount of formulas each symbol occurs in.
+    let mut occurrences = HashMap::<i64, usize>::default();
+    for &s in axiom_symbols.iter().chain(goal_symbols.iter()).flatten() {
+        *occurrences.entry(s).or_insert(0) += 1;
+    }
+
+    // An axiom is triggered by its rarest symbols, with some tolerance.
+    let mut triggers = HashMap::<i64, Vec<usize>>::default();
+    for (i, symbols) in axiom_symbols.iter().enumerate() {
+        if let Some(min_occurrences) = symbols.iter().map(|s| occurrences[s]).min() {
+            for &s in symbols {
+                if occurrences[&s] as f64 <= tolerance * min_occurrences as f64 {
+                    triggers.entry(s).or_insert_with(Vec::new).push(i);
+                }
+            }
+        }
+    }
+
+    let mut selected = axiom_symbols
+        .iter()
+        .map(|symbols| symbols.is_empty())
+        .collect::<Vec<_>>();
+    let mut reached = goal_symbols
+        .into_iter()
+        .flatten()
+        .map(|s| (s, ()))
+        .collect::<HashMap<_, _>>();
+    let mut current = reached.keys().cloned().collect::<Vec<_>>();
+    let mut level = 0;
+
+    while !current.is_empty() && depth.is_none_or(|d| level < d) {
+        let mut next = Vec::new();
+        for s in current {
+            for &i in triggers.get(&s).map(|v| v.as_slice()).unwrap_or(&[]) {
+                if !selected[i] {
+                    selected[i] = true;
+                    for &s2 in &axiom_symbols[i] {
+                        if reached.insert(s2, ()).is_none() {
+                            next.push(s2);
+                        }
+                    }
+                }
+            }
+        }
+        current = next;
+        level += 1;
+    }
+
+    axioms
+        .into_iter()
+        .zip(selected)
+        .filter(|&(_, selected)| selected)
+        .map(|(x, _)| x)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::sine_select;
+    use crate::cnf::ast::{Formula, Term};
+
+    fn pred(id: i64, args: Vec<Term>) -> Formula {
+        Formula::Predicate(id, args)
+    }
+
+    fn constant(id: i64) -> Term {
+        Term::Function(id, Vec::new())
+    }
+
+    #[test]
+    fn sine_select_1() {
+        // The goal p(a) reaches p(a) => q(a) and through q the axiom q(a).
+        let x = Term::Variable(-1);
+        let goal = pred(1, vec![constant(10)]);
+        let ax1 = Formula::Implies(
+            Box::new(pred(1, vec![constant(10)])),
+            Box::new(pred(2, vec![constant(10)])),
+        );
+        let ax2 = pred(2, vec![constant(10)]);
+        let ax3 = pred(4, vec![constant(12)]);
+        let ax4 = Formula::Forall(-1, Box::new(pred(0, vec![x.clone(), x])));
+        let axioms = vec![(ax1, 1), (ax2, 2), (ax3, 3), (ax4, 4)];
+
+        let all = sine_select(axioms.clone(), &[&goal], 1.0, None);
+        let shallow = sine_select(axioms.clone(), &[&goal], 1.0, Some(1));
+        let no_goals = sine_select(axioms, &[], 1.0, None);
+
+        assert_eq!(all.iter().map(|x| x.1).collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert_eq!(shallow.iter().map(|x| x.1).collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(no_goals.len(), 4);
+    }
+
+    #[test]
+    fn sine_select_2() {
+        // The symbol q occurs twice as often as d, so only a higher tolerance lets q trigger q(d).
+        let goal = pred(1, vec![constant(10)]);
+        let ax1 = pred(1, vec![constant(12)]);
+        let ax2 = pred(2, vec![constant(12)]);
+        let ax3 = pred(2, vec![constant(13)]);
+        let axioms = vec![(ax1, 1), (ax2, 2), (ax3, 3)];
+
+        let strict = sine_select(axioms.clone(), &[&goal], 1.0, None);
+        let tolerant = sine_select(axioms, &[&goal], 2.0, None);
+
+        assert_eq!(strict.iter().map(|x| x.1).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            tolerant.iter().map(|x| x.1).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+}
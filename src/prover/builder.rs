@@ -0,0 +1,545 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::cnf::ast_transformer::{tptp_str_to_cnf_ast, tptp_to_cnf_ast};
+use crate::prover::axiom_selection::AxiomSelection;
+use crate::prover::clause_selection::heuristic::{validate_heuristics, Heuristic};
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::inference::literal_selection::LiteralSelection;
+use crate::prover::proof_result::ProofResult;
+use crate::prover::proof_search::{prove_clauses, prove_tptp};
+use crate::prover::proof_statistics::ProofStatistics;
+use crate::prover::resource_limits::{CancellationToken, ResourceLimits};
+use crate::tptp_parser::include_resolver::{FileSystemResolver, IncludeResolver};
+use crate::utils::stopwatch::Stopwatch;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Contains the options for a proof search.
+/// Options are set builder-style, after which the prover can be run on several problems.
+#[derive(Debug, Clone)]
+pub struct Prover {
+    use_lpo: bool,
+    renaming_limit: u64,
+    time_limit_ms: Option<u64>,
+    heuristics: Vec<(Heuristic, usize)>,
+    axiom_selection: AxiomSelection,
+    set_of_support: bool,
+    literal_selection: LiteralSelection,
+    limits: ResourceLimits,
+    cancellation_token: CancellationToken,
+    include_resolver: Arc<dyn IncludeResolver + Send + Sync>,
+}
+
+impl Default for Prover {
+    fn default() -> Prover {
+        Prover {
+            use_lpo: false,
+            renaming_limit: 32,
+            time_limit_ms: None,
+            heuristics: vec![(Heuristic::Size(2, 1), 4), (Heuristic::Age, 1)],
+            axiom_selection: AxiomSelection::All,
+            set_of_support: false,
+            literal_selection: LiteralSelection::None,
+            limits: ResourceLimits::new(),
+            cancellation_token: CancellationToken::new(),
+            include_resolver: Arc::new(FileSystemResolver::new()),
+        }
+    }
+}
+
+impl Prover {
+    /// Creates a prover with the default options: KBO, renaming limit 32 and no time limit.
+    pub fn new() -> Prover {
+        Prover::default()
+    }
+
+    /// Use LPO instead of KBO as the term ordering.
+    pub fn use_lpo(mut self, use_lpo: bool) -> Prover {
+        self.use_lpo = use_lpo;
+        self
+    }
+
+    /// Set the limit for renaming subformulae in the CNF transformation. 0 disables renaming.
+    pub fn renaming_limit(mut self, renaming_limit: u64) -> Prover {
+        self.renaming_limit = renaming_limit;
+        self
+    }
+
+    /// Set the time limit of a proof search in milliseconds.
+    pub fn time_limit_ms(mut self, time_limit_ms: u64) -> Prover {
+        self.time_limit_ms = Some(time_limit_ms);
+        self
+    }
+
+    /// Set the heuristics for picking the given clause.
+    /// Each heuristic is given with the amount of times it is used in a row.
+    /// Fails if none of the heuristics would ever be used.
+    pub fn heuristics(mut self, heuristics: Vec<(Heuristic, usize)>) -> Result<Prover, String> {
+        validate_heuristics(&heuristics)?;
+        self.heuristics = heuristics;
+        Ok(self)
+    }
+
+    /// Set how the axioms given to the proof search are selected. By default all axioms are used.
+    /// If some axioms are left out, the problem can no longer be found satisfiable.
+    pub fn axiom_selection(mut self, axiom_selection: AxiomSelection) -> Prover {
+        self.axiom_selection = axiom_selection;
+        self
+    }
+
+    /// Use the set of support restriction, so that clauses which don't come from the negated
+    /// conjecture are only used in inferences with clauses which do.
+    /// The problem can then no longer be found satisfiable.
+    pub fn set_of_support(mut self, set_of_support: bool) -> Prover {
+        self.set_of_support = set_of_support;
+        self
+    }
+
+    /// Set how negative literals are selected for inferences. By default nothing is selected.
+    pub fn literal_selection(mut self, literal_selection: LiteralSelection) -> Prover {
+        self.literal_selection = literal_selection;
+        self
+    }
+
+    /// Set the maximum amount of given clauses to analyze before giving up.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Prover {
+        self.limits.set_max_iterations(max_iterations);
+        self
+    }
+
+    /// Set the maximum amount of clauses generated by inferences before giving up.
+    pub fn max_generated_clauses(mut self, max_generated_clauses: usize) -> Prover {
+        self.limits.set_max_generated_clauses(max_generated_clauses);
+        self
+    }
+
+    /// Set the (estimated) maximum amount of memory used for storing clauses, in megabytes.
+    pub fn memory_limit_mb(mut self, memory_limit_mb: usize) -> Prover {
+        self.limits
+            .set_max_memory_bytes(memory_limit_mb.saturating_mul(1024 * 1024));
+        self
+    }
+
+    /// Set a token which can be used for stopping the proof searches from another thread.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Prover {
+        self.cancellation_token = token;
+        self
+    }
+
+    /// Set how the files named in include directives are found. By default they are read from disk.
+    pub fn include_resolver<R>(mut self, resolver: R) -> Prover
+    where
+        R: IncludeResolver + Send + Sync + 'static,
+    {
+        self.include_resolver = Arc::new(resolver);
+        self
+    }
+
+    /// Check if LPO is used instead of KBO.
+    pub fn get_use_lpo(&self) -> bool {
+        self.use_lpo
+    }
+
+    /// Get the renaming limit for the CNF transformation.
+    pub fn get_renaming_limit(&self) -> u64 {
+        self.renaming_limit
+    }
+
+    /// Get the time limit in milliseconds, if any.
+    pub fn get_time_limit_ms(&self) -> Option<u64> {
+        self.time_limit_ms
+    }
+
+    /// Get the heuristics for picking the given clause.
+    pub fn get_heuristics(&self) -> &[(Heuristic, usize)] {
+        &self.heuristics
+    }
+
+    /// Get the way axioms are selected for the proof search.
+    pub fn get_axiom_selection(&self) -> AxiomSelection {
+        self.axiom_selection
+    }
+
+    /// Check if the set of support restriction is used.
+    pub fn get_set_of_support(&self) -> bool {
+        self.set_of_support
+    }
+
+    /// Get the way negative literals are selected for inferences.
+    pub fn get_literal_selection(&self) -> LiteralSelection {
+        self.literal_selection
+    }
+
+    /// Get the limits on the resources of a proof search.
+    pub fn get_resource_limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    /// Get the token for stopping the proof search.
+    pub fn get_cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    /// Attempts to prove the problem in the TPTP file at the location given.
+    pub fn prove_file(&self, path: &str) -> ProverOutput {
+        let path = path.to_owned();
+        self.run(move |options, stats| {
+            let parsed = tptp_to_cnf_ast(&path, options.include_resolver.as_ref());
+            prove_tptp(parsed, &path, options, stats)
+        })
+    }
+
+    /// Attempts to prove a problem in TPTP format given as a string.
+    pub fn prove_str(&self, problem: &str) -> ProverOutput {
+        let problem = problem.to_owned();
+        self.run(move |options, stats| {
+            let parsed = tptp_str_to_cnf_ast(&problem, options.include_resolver.as_ref());
+            prove_tptp(parsed, "problem", options, stats)
+        })
+    }
+
+    /// Attempts to derive a contradiction from a set of clauses.
+    pub fn prove_clauses(&self, clauses: Vec<Clause>) -> ProverOutput {
+        self.run(move |options, stats| prove_clauses(clauses, options, stats))
+    }
+
+    /// Runs a proof search in a separate thread and waits for it to finish or time out.
+    /// A search which times out is cancelled. Note that parsing and CNF transformation
+    /// cannot be interrupted, so the thread might keep running for a while after that.
+    fn run<F>(&self, search: F) -> ProverOutput
+    where
+        F: FnOnce(&Prover, &ProofStatistics) + Send + 'static,
+    {
+        let stats = Arc::new(ProofStatistics::new());
+        let search_stats = Arc::clone(&stats);
+        // Each search gets its own token so that timing out does not cancel later searches.
+        let token = self.cancellation_token.child();
+        let mut options = self.clone();
+        options.cancellation_token = token.clone();
+
+        // The stack size is a hack to get around the parser/CNF transformer from crashing with very large files.
+        let handle = thread::Builder::new()
+            .stack_size(32 * 1024 * 1024)
+            .spawn(move || search(&options, &search_stats))
+            .expect("Creating a new thread shouldn't fail");
+
+        let timed_out = if let Some(time_limit_ms) = self.time_limit_ms {
+            let mut sw = Stopwatch::new();
+            let resolution = Duration::from_millis(10);
+
+            sw.start();
+            while sw.elapsed_ms() < time_limit_ms && !handle.is_finished() {
+                thread::sleep(resolution);
+            }
+            sw.stop();
+            !handle.is_finished()
+        } else {
+            false
+        };
+
+        // A search which panicked never finishes, so it has to be told apart from one which timed out.
+        let result = if timed_out {
+            if !stats.has_search_finished() {
+                token.cancel();
+            }
+            stats.get_proof_result()
+        } else if handle.join().is_err() {
+            ProofResult::Error("The proof search panicked".to_owned())
+        } else {
+            stats.get_proof_result()
+        };
+
+        ProverOutput::new(result, stats)
+    }
+}
+
+/// The outcome of a proof search run with `Prover`.
+#[derive(Debug)]
+pub struct ProverOutput {
+    result: ProofResult,
+    statistics: Arc<ProofStatistics>,
+    proof: Option<Vec<String>>,
+    unsat_core: Option<Vec<String>>,
+}
+
+impl ProverOutput {
+    /// Collects the outcome of a proof search.
+    fn new(result: ProofResult, statistics: Arc<ProofStatistics>) -> ProverOutput {
+        let proof = if result.is_refutation() || result.is_saturation() {
+            Some(statistics.get_proof_output())
+        } else {
+            None
+        };
+
+        let unsat_core = if result.is_refutation() {
+            Some(statistics.get_unsat_core())
+        } else {
+            None
+        };
+
+        ProverOutput {
+            result,
+            statistics,
+            proof,
+            unsat_core,
+        }
+    }
+
+    /// Get the result of the proof search.
+    pub fn get_result(&self) -> &ProofResult {
+        &self.result
+    }
+
+    /// Get the statistics collected during the proof search.
+    pub fn get_statistics(&self) -> &ProofStatistics {
+        &self.statistics
+    }
+
+    /// Get the TSTP refutation or saturated clause set, if the search finished successfully.
+    pub fn get_proof(&self) -> Option<&[String]> {
+        self.proof.as_deref()
+    }
+
+    /// Get the names of the input formulas used in the refutation, if one was found.
+    /// Problems given as clauses have no names, so their unsat core is always empty.
+    pub fn get_unsat_core(&self) -> Option<&[String]> {
+        self.unsat_core.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Prover;
+    use crate::prover::axiom_selection::AxiomSelection;
+    use crate::prover::clause_selection::heuristic::Heuristic;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::prover::inference::literal_selection::LiteralSelection;
+    use crate::prover::proof_result::ProofResult;
+    use crate::prover::resource_limits::CancellationToken;
+    use crate::tptp_parser::include_resolver::{DenyIncludes, InMemoryResolver};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn prove_file_1() {
+        let output = Prover::new()
+            .time_limit_ms(10000)
+            .prove_file("test_problems/p1.p");
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+        assert!(!output.get_proof().expect("Proof should exist").is_empty());
+    }
+
+    #[test]
+    fn prove_str_1() {
+        let problem = "fof(a, axiom, p(a)). fof(b, conjecture, ?[X]: p(X)).";
+        let output = Prover::new().use_lpo(true).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+        assert!(output.get_statistics().get_iteration_count() > 0);
+    }
+
+    #[test]
+    fn prove_str_origins() {
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("A.ax", "fof(a_1, axiom, p(a), unknown).");
+        let problem = "include('A.ax'). fof('Goal', conjecture, ?[X]: p(X)).";
+        let output = Prover::new().include_resolver(resolver).prove_str(problem);
+        let proof = output.get_proof().expect("Proof should exist");
+
+        assert!(proof.iter().any(|l| l.contains("file('A.ax',a_1)")));
+        assert!(proof
+            .iter()
+            .any(|l| l.starts_with("cnf(c_1,negated_conjecture,")
+                && l.contains("file('problem','Goal')")));
+    }
+
+    #[test]
+    fn prove_str_unsat_core() {
+        let problem =
+            "fof(a, axiom, p(a)). fof(b, axiom, q(b)). fof(c, axiom, ![X]: (p(X) => r(X))). \
+                       fof('Goal', conjecture, r(a)).";
+        let output = Prover::new().prove_str(problem);
+        let core = output.get_unsat_core().expect("Unsat core should exist");
+
+        assert_eq!(core, &["a", "c", "'Goal'"]);
+        assert!(Prover::new()
+            .prove_str("fof(a, axiom, p(a)).")
+            .get_unsat_core()
+            .is_none());
+    }
+
+    #[test]
+    fn prove_str_axiom_selection() {
+        let sine = AxiomSelection::Sine {
+            tolerance: 1.5,
+            depth: None,
+            min_axioms: 0,
+        };
+        let problem =
+            "fof(a, axiom, p(a)). fof(b, axiom, q(b)). fof(c, axiom, ![X]: (p(X) => r(X))). \
+                       fof('Goal', conjecture, r(a)).";
+        let output = Prover::new().axiom_selection(sine).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+
+        // Leaving out axioms means that saturation doesn't prove anything.
+        let problem = "fof(a, axiom, p(a)). fof(b, axiom, q(b)). fof('Goal', conjecture, p(b)).";
+        let output = Prover::new().axiom_selection(sine).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::GaveUp);
+    }
+
+    #[test]
+    fn prove_str_2() {
+        let output = Prover::new()
+            .heuristics(vec![(Heuristic::Age, 1)])
+            .expect("Age is used")
+            .prove_str("cnf(a, axiom, p(X) | q(X)).");
+        assert_eq!(*output.get_result(), ProofResult::Satisfiable);
+    }
+
+    #[test]
+    fn heuristics_invalid() {
+        assert!(Prover::new().heuristics(Vec::new()).is_err());
+        assert!(Prover::new().heuristics(vec![(Heuristic::Age, 0)]).is_err());
+    }
+
+    #[test]
+    fn prove_str_heuristics() {
+        let problem = "fof(a, axiom, ![X]: (p(X) => q(f(X)))). fof(b, axiom, p(a)). \
+                       fof(c, axiom, r(b)). fof('Goal', conjecture, ?[X]: q(X)).";
+        for heuristic in &[
+            Heuristic::GoalDirected(2, 1),
+            Heuristic::LiteralCount,
+            Heuristic::Depth,
+            Heuristic::Conjecture,
+        ] {
+            let output = Prover::new()
+                .heuristics(vec![(*heuristic, 1)])
+                .expect("The heuristic is used")
+                .prove_str(problem);
+            assert_eq!(*output.get_result(), ProofResult::Theorem);
+        }
+    }
+
+    #[test]
+    fn prove_str_set_of_support() {
+        let problem = "fof(a, axiom, ![X]: (p(X) => q(X))). fof(b, axiom, ![X]: (q(X) => r(X))). \
+                       fof(c, axiom, p(a)). fof('Goal', conjecture, r(a)).";
+        let output = Prover::new().set_of_support(true).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+
+        // The axioms are never saturated, so nothing can be said about the conjecture.
+        let problem = "fof(a, axiom, p(a)). fof('Goal', conjecture, p(b)).";
+        let output = Prover::new().set_of_support(true).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::GaveUp);
+        let output = Prover::new()
+            .set_of_support(true)
+            .prove_str("fof(a, axiom, p(a)).");
+        assert_eq!(*output.get_result(), ProofResult::Satisfiable);
+    }
+
+    #[test]
+    fn prove_str_literal_selection() {
+        let theorem = "fof(a, axiom, ![X]: (p(X) => q(f(X)))). fof(b, axiom, p(a)). \
+                       fof(c, axiom, ![X, Y]: ((q(X) & q(Y)) => X = Y)). \
+                       fof('Goal', conjecture, ![X]: (p(X) => f(X) = f(a))).";
+        let satisfiable = "cnf(a, axiom, ~p(X) | p(f(X))). cnf(b, axiom, ~q(X) | q(X) | r(X)).";
+        for selection in &[
+            LiteralSelection::MaxWeightNegative,
+            LiteralSelection::AllNegative,
+            LiteralSelection::Complex,
+        ] {
+            let prover = Prover::new().literal_selection(*selection);
+            assert_eq!(
+                *prover.prove_str(theorem).get_result(),
+                ProofResult::Theorem
+            );
+            assert_eq!(
+                *prover.prove_str(satisfiable).get_result(),
+                ProofResult::Satisfiable
+            );
+        }
+    }
+
+    #[test]
+    fn prove_str_3() {
+        let output = Prover::new().prove_str("fof(a, axiom, p(a)");
+        assert!(output.get_result().is_err());
+        assert!(output.get_proof().is_none());
+    }
+
+    #[test]
+    fn prove_str_4() {
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("Axioms/P.ax", "fof(a, axiom, ![X]: (p(X) => q(X))).");
+        let problem = "include('Axioms/P.ax'). fof(b, axiom, p(c)). fof(d, conjecture, q(c)).";
+        let output = Prover::new().include_resolver(resolver).prove_str(problem);
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+
+        let output = Prover::new()
+            .include_resolver(DenyIncludes)
+            .prove_str(problem);
+        assert!(output.get_result().is_err());
+    }
+
+    #[test]
+    fn max_iterations_1() {
+        let output = Prover::new()
+            .max_iterations(2)
+            .prove_file("test_problems/group_left_inverse_means_right_inverse.p");
+        assert_eq!(*output.get_result(), ProofResult::ResourceOut);
+        assert_eq!(output.get_statistics().get_iteration_count(), 2);
+        assert!(output.get_proof().is_none());
+    }
+
+    #[test]
+    fn cancellation_token_1() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let output = Prover::new()
+            .cancellation_token(token)
+            .prove_file("test_problems/p1.p");
+        assert_eq!(*output.get_result(), ProofResult::Timeout);
+    }
+
+    #[test]
+    fn prove_clauses_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let f_a = Term::new_function(2, vec![a.clone()]);
+        let cl1 = Clause::new(vec![Literal::new(false, f_x, x)]);
+        let cl2 = Clause::new(vec![Literal::new(true, f_a, a)]);
+
+        let output = Prover::new().prove_clauses(vec![cl1, cl2]);
+        assert_eq!(*output.get_result(), ProofResult::Unsatisfiable);
+    }
+
+    #[test]
+    fn prove_clauses_panic() {
+        // Using the same function symbol with different arities makes the term ordering panic.
+        let a = Term::new_constant(1);
+        let f_a = Term::new_function(2, vec![a.clone()]);
+        let f_a_a = Term::new_function(2, vec![a.clone(), a]);
+        let cl = Clause::new(vec![Literal::new(true, f_a, f_a_a)]);
+
+        let start = Instant::now();
+        let output = Prover::new().time_limit_ms(60000).prove_clauses(vec![cl]);
+        assert!(output.get_result().is_err());
+        assert!(start.elapsed() < Duration::from_millis(60000));
+    }
+}
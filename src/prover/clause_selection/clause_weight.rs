@@ -15,10 +15,11 @@
 //
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::HashSet;
 use std::cmp::Ordering;
 
 /// Different ways to give weights to clauses.
-/// Trying to order different variants in the enum results in a panic.
 #[derive(PartialEq, Eq)]
 pub enum ClauseWeight {
     /// Based on the size of the clause.
@@ -26,6 +27,36 @@ pub enum ClauseWeight {
     Size(u64, u64),
     /// Based on the age of the clause. Contains the ID of the clause.
     Age(u64),
+    /// Based on the amount of literals in the clause.
+    /// Contains the ID and the literal count of the clause.
+    LiteralCount(u64, usize),
+    /// Based on the depth of the deepest term in the clause.
+    /// Contains the ID and the depth of the clause.
+    Depth(u64, usize),
+    /// Based on whether the clause comes from the negated conjecture, and then on its size.
+    /// Contains the ID, whether the clause comes from the negated conjecture and its symbol count.
+    Conjecture(u64, bool, u64),
+}
+
+/// Calculates the symbol count of a term, counting the symbols not in the goal twice.
+fn goal_symbol_count(t: &Term, goal_symbols: &HashSet<i64>, f_value: u64, v_value: u64) -> u64 {
+    if t.is_variable() {
+        v_value
+    } else {
+        let value = if goal_symbols.contains(&t.get_id()) {
+            f_value
+        } else {
+            2 * f_value
+        };
+        t.iter().fold(value, |acc, sub_t| {
+            acc + goal_symbol_count(sub_t, goal_symbols, f_value, v_value)
+        })
+    }
+}
+
+/// Calculates the depth of a term. Variables and constants have depth 1.
+fn term_depth(t: &Term) -> usize {
+    1 + t.iter().map(term_depth).max().unwrap_or(0)
 }
 
 impl ClauseWeight {
@@ -36,15 +67,57 @@ impl ClauseWeight {
         ClauseWeight::Size(cl.get_id(), cl.symbol_count(f_value, v_value))
     }
 
+    /// Creates a new weight based on the symbol count of the clause,
+    /// where function symbols which don't occur in the goal count twice.
+    pub fn new_goal_weight(
+        cl: &Clause,
+        goal_symbols: &HashSet<i64>,
+        f_value: u64,
+        v_value: u64,
+    ) -> ClauseWeight {
+        let weight = cl
+            .iter()
+            .flat_map(|l| l.iter())
+            .map(|t| goal_symbol_count(t, goal_symbols, f_value, v_value))
+            .sum();
+        ClauseWeight::Size(cl.get_id(), weight)
+    }
+
     /// Creates a new weight based on the age of the clause.
     pub fn new_age_weight(clause: &Clause) -> ClauseWeight {
         ClauseWeight::Age(clause.get_id())
     }
 
+    /// Creates a new weight based on the amount of literals in the clause.
+    pub fn new_literal_count_weight(cl: &Clause) -> ClauseWeight {
+        ClauseWeight::LiteralCount(cl.get_id(), cl.size())
+    }
+
+    /// Creates a new weight based on the depth of the deepest term in the clause.
+    pub fn new_depth_weight(cl: &Clause) -> ClauseWeight {
+        let depth = cl
+            .iter()
+            .flat_map(|l| l.iter())
+            .map(term_depth)
+            .max()
+            .unwrap_or(0);
+        ClauseWeight::Depth(cl.get_id(), depth)
+    }
+
+    /// Creates a new weight which prefers clauses coming from the negated conjecture.
+    /// Clauses in the same group are compared by their symbol count.
+    pub fn new_conjecture_weight(cl: &Clause) -> ClauseWeight {
+        ClauseWeight::Conjecture(cl.get_id(), cl.is_from_conjecture(), cl.symbol_count(2, 1))
+    }
+
     /// Get the ID of the clause a particular clause weight is associated with.
     pub fn get_id(&self) -> u64 {
         match *self {
-            ClauseWeight::Size(id, _) | ClauseWeight::Age(id) => id,
+            ClauseWeight::Size(id, _)
+            | ClauseWeight::Age(id)
+            | ClauseWeight::LiteralCount(id, _)
+            | ClauseWeight::Depth(id, _)
+            | ClauseWeight::Conjecture(id, _, _) => id,
         }
     }
 }
@@ -58,12 +131,77 @@ impl PartialOrd for ClauseWeight {
 impl Ord for ClauseWeight {
     fn cmp(&self, other: &ClauseWeight) -> Ordering {
         // Since BinaryHeap is a max heap, the comparison is inverted.
+        // Ties in literal count and depth are common, so those are broken by age.
         match (self, other) {
             (&ClauseWeight::Size(_, weight1), &ClauseWeight::Size(_, weight2)) => {
                 weight2.cmp(&weight1)
             }
             (&ClauseWeight::Age(id1), &ClauseWeight::Age(id2)) => id2.cmp(&id1),
+            (&ClauseWeight::LiteralCount(id1, n1), &ClauseWeight::LiteralCount(id2, n2))
+            | (&ClauseWeight::Depth(id1, n1), &ClauseWeight::Depth(id2, n2)) => {
+                n2.cmp(&n1).then(id2.cmp(&id1))
+            }
+            (
+                &ClauseWeight::Conjecture(_, conj1, weight1),
+                &ClauseWeight::Conjecture(_, conj2, weight2),
+            ) => conj1.cmp(&conj2).then(weight2.cmp(&weight1)),
             _ => panic!("faulty comparision"),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ClauseWeight;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::utils::hash_map::HashSet;
+
+    fn clause(id: u64, lhs: Term, rhs: Term) -> Clause {
+        let mut cl = Clause::new(vec![Literal::new(false, lhs, rhs)]);
+        cl.set_id(id);
+        cl
+    }
+
+    #[test]
+    fn goal_weight_1() {
+        // f(x) = c where only f occurs in the goal.
+        let x = Term::new_variable(-1);
+        let f_x = Term::new_function(1, vec![x]);
+        let cl = clause(0, f_x, Term::new_constant(2));
+        let goal_symbols = [1].iter().cloned().collect::<HashSet<_>>();
+
+        assert!(
+            ClauseWeight::new_goal_weight(&cl, &goal_symbols, 2, 1) == ClauseWeight::Size(0, 7)
+        );
+        assert!(ClauseWeight::new_size_weight(&cl, 2, 1) == ClauseWeight::Size(0, 5));
+    }
+
+    #[test]
+    fn depth_weight_1() {
+        let c = Term::new_constant(1);
+        let f_f_c = Term::new_function(2, vec![Term::new_function(2, vec![c.clone()])]);
+        let deep = clause(0, f_f_c, c.clone());
+        let shallow = clause(1, c.clone(), c);
+
+        assert!(ClauseWeight::new_depth_weight(&deep) == ClauseWeight::Depth(0, 3));
+        assert!(ClauseWeight::new_depth_weight(&shallow) > ClauseWeight::new_depth_weight(&deep));
+    }
+
+    #[test]
+    fn conjecture_weight_1() {
+        let c = Term::new_constant(1);
+        let f_c = Term::new_function(2, vec![c.clone()]);
+        let small = clause(0, c.clone(), c.clone());
+        let mut big = clause(1, f_c, c);
+        big.set_from_conjecture(true);
+
+        assert!(
+            ClauseWeight::new_conjecture_weight(&big) > ClauseWeight::new_conjecture_weight(&small)
+        );
+        assert!(
+            ClauseWeight::new_size_weight(&big, 2, 1) < ClauseWeight::new_size_weight(&small, 2, 1)
+        );
+    }
+}
@@ -16,10 +16,11 @@
 
 use crate::prover::clause_selection::clause_weight::ClauseWeight;
 use crate::prover::data_structures::clause::Clause;
+use crate::utils::hash_map::HashSet;
+use std::str::FromStr;
 
 /// Different heuristics for ordering clauses.
-#[allow(dead_code)]
-#[derive(PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Heuristic {
     /// Heuristic based on the symbol count of the clause.
     /// First number is the value to give to functions.
@@ -27,14 +28,147 @@ pub enum Heuristic {
     Size(u64, u64),
     /// Heuristic based on clause age.
     Age,
+    /// Like `Size`, except that function symbols which don't occur in the goal count twice.
+    /// The goal is formed by the clauses coming from the negated conjecture.
+    GoalDirected(u64, u64),
+    /// Heuristic based on the amount of literals in the clause.
+    LiteralCount,
+    /// Heuristic based on the depth of the deepest term in the clause.
+    Depth,
+    /// Prefers clauses coming from the negated conjecture, smallest first.
+    Conjecture,
 }
 
 impl Heuristic {
     /// Creates a new ClauseWeight corresponding to the heuristic for a given clause.
-    pub fn new_clauseweight(&self, cl: &Clause) -> ClauseWeight {
+    /// The goal symbols are the symbols occurring in the clauses coming from the negated conjecture.
+    pub fn new_clauseweight(&self, cl: &Clause, goal_symbols: &HashSet<i64>) -> ClauseWeight {
         match *self {
             Heuristic::Size(f_val, v_val) => ClauseWeight::new_size_weight(cl, f_val, v_val),
             Heuristic::Age => ClauseWeight::new_age_weight(cl),
+            Heuristic::GoalDirected(f_val, v_val) => {
+                ClauseWeight::new_goal_weight(cl, goal_symbols, f_val, v_val)
+            }
+            Heuristic::LiteralCount => ClauseWeight::new_literal_count_weight(cl),
+            Heuristic::Depth => ClauseWeight::new_depth_weight(cl),
+            Heuristic::Conjecture => ClauseWeight::new_conjecture_weight(cl),
         }
     }
 }
+
+/// Parses the two arguments of a heuristic like `size(2,1)`.
+fn parse_arguments(name: &str, args: &str) -> Result<(u64, u64), String> {
+    let args = args
+        .split(',')
+        .map(|x| x.trim().parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("Invalid arguments for the {} heuristic", name))?;
+    if let [f_val, v_val] = args[..] {
+        Ok((f_val, v_val))
+    } else {
+        Err(format!("The {} heuristic takes two arguments", name))
+    }
+}
+
+impl FromStr for Heuristic {
+    type Err = String;
+
+    /// Parses a heuristic written like `age`, `literals`, `depth`, `conjecture`,
+    /// `size(2,1)` or `goal(2,1)`.
+    fn from_str(s: &str) -> Result<Heuristic, String> {
+        let s = s.trim();
+        match s {
+            "age" => return Ok(Heuristic::Age),
+            "literals" => return Ok(Heuristic::LiteralCount),
+            "depth" => return Ok(Heuristic::Depth),
+            "conjecture" => return Ok(Heuristic::Conjecture),
+            _ => {}
+        }
+
+        let (name, args) = s
+            .strip_suffix(')')
+            .and_then(|rest| rest.split_once('('))
+            .ok_or_else(|| format!("Unknown heuristic {}", s))?;
+        match name.trim() {
+            "size" => parse_arguments("size", args).map(|(f, v)| Heuristic::Size(f, v)),
+            "goal" => parse_arguments("goal", args).map(|(f, v)| Heuristic::GoalDirected(f, v)),
+            _ => Err(format!("Unknown heuristic {}", s)),
+        }
+    }
+}
+
+/// Parses a list of heuristics with the amount of times each is used in a row,
+/// written like `size(2,1):4,age:1`.
+pub fn parse_heuristics(s: &str) -> Result<Vec<(Heuristic, usize)>, String> {
+    let mut heuristics = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    // Commas inside the arguments of a heuristic don't separate heuristics.
+    for (i, c) in s.char_indices().chain(Some((s.len(), ','))) {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                let item = &s[start..i];
+                let (heuristic, count) = item
+                    .rsplit_once(':')
+                    .ok_or_else(|| format!("Expected heuristic:count, got {}", item.trim()))?;
+                let count = count
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid count in {}", item.trim()))?;
+                heuristics.push((heuristic.parse()?, count));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    validate_heuristics(&heuristics)?;
+    Ok(heuristics)
+}
+
+/// Checks that a list of heuristics with the amount of times each is used in a row uses at least one of them.
+pub fn validate_heuristics(heuristics: &[(Heuristic, usize)]) -> Result<(), String> {
+    if heuristics.iter().all(|&(_, n)| n == 0) {
+        Err("At least one heuristic needs to be used".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_heuristics, Heuristic};
+
+    #[test]
+    fn parse_heuristics_1() {
+        assert_eq!(
+            parse_heuristics("size(2,1):4,age:1"),
+            Ok(vec![(Heuristic::Size(2, 1), 4), (Heuristic::Age, 1)])
+        );
+        assert_eq!(
+            parse_heuristics(" size( 3 , 2 ) : 1 "),
+            Ok(vec![(Heuristic::Size(3, 2), 1)])
+        );
+        assert_eq!(
+            parse_heuristics("goal(3,1):2,literals:1,depth:1,conjecture:1"),
+            Ok(vec![
+                (Heuristic::GoalDirected(3, 1), 2),
+                (Heuristic::LiteralCount, 1),
+                (Heuristic::Depth, 1),
+                (Heuristic::Conjecture, 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_heuristics_2() {
+        assert!(parse_heuristics("").is_err());
+        assert!(parse_heuristics("age").is_err());
+        assert!(parse_heuristics("age:0").is_err());
+        assert!(parse_heuristics("size(2):1").is_err());
+        assert!(parse_heuristics("weight:1").is_err());
+        assert!(parse_heuristics("age(1,2):1").is_err());
+    }
+}
@@ -55,7 +55,7 @@ pub fn choose_heuristic(
     // If we didn't find anything we need to reset the counter.
     assert_eq!(
         *current_heuristic_count,
-        heuristic_use_count.iter().fold(0, |acc, x| acc + x)
+        heuristic_use_count.iter().sum::<usize>()
     );
     *current_heuristic_count = 1;
     0
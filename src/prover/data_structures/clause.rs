@@ -14,9 +14,11 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::prover::data_structures::derivation::{Derivation, InferenceRule};
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::unification::substitution::Substitution;
 use std::fmt::{Debug, Error, Formatter};
+use std::mem;
 use std::ops::{Index, IndexMut};
 use std::slice::{Iter, IterMut};
 
@@ -26,14 +28,20 @@ use std::slice::{Iter, IterMut};
 pub struct Clause {
     id: Option<u64>,
     literals: Vec<Literal>,
+    derivation: Derivation,
+    origins: Vec<usize>,
+    from_conjecture: bool,
 }
 
 impl Clause {
-    /// Creates a new clause.
+    /// Creates a new input clause.
     pub fn new(literals: Vec<Literal>) -> Clause {
         Clause {
             id: None,
-            literals: literals,
+            literals,
+            derivation: Derivation::Input,
+            origins: Vec::new(),
+            from_conjecture: false,
         }
     }
 
@@ -60,12 +68,12 @@ impl Clause {
     }
 
     /// Used for iterating the literals of the clause.
-    pub fn iter(&self) -> Iter<Literal> {
+    pub fn iter(&self) -> Iter<'_, Literal> {
         self.literals.iter()
     }
 
     /// Used for iterating the literals of the clause with the option of mutating them.
-    pub fn iter_mut(&mut self) -> IterMut<Literal> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, Literal> {
         self.literals.iter_mut()
     }
 
@@ -109,6 +117,48 @@ impl Clause {
     pub fn get_id(&self) -> u64 {
         self.id.expect("ID should always exist")
     }
+
+    /// Get a reference to the derivation of the clause.
+    pub fn get_derivation(&self) -> &Derivation {
+        &self.derivation
+    }
+
+    /// Set the derivation of the clause.
+    /// The old derivation is forgotten, so the clause no longer has any origins either.
+    pub fn set_derivation(&mut self, derivation: Derivation) {
+        self.derivation = derivation;
+        self.origins.clear();
+    }
+
+    /// Get the IDs of the input formulas the clause was created from.
+    /// Only input clauses have origins, and clauses given directly to the prover don't have any.
+    /// Simplifying a clause keeps its origins.
+    pub fn get_origins(&self) -> &[usize] {
+        &self.origins
+    }
+
+    /// Set the IDs of the input formulas the clause was created from.
+    pub fn set_origins(&mut self, origins: Vec<usize>) {
+        self.origins = origins;
+    }
+
+    /// Checks if the clause comes from the negated conjecture, either directly or through inferences.
+    pub fn is_from_conjecture(&self) -> bool {
+        self.from_conjecture
+    }
+
+    /// Set whether the clause comes from the negated conjecture.
+    pub fn set_from_conjecture(&mut self, from_conjecture: bool) {
+        self.from_conjecture = from_conjecture;
+    }
+
+    /// Records that the clause was simplified by the given rule with the clauses with the given IDs.
+    pub fn add_simplification(&mut self, rule: InferenceRule, premises: Vec<u64>) {
+        let old_derivation = mem::replace(&mut self.derivation, Derivation::Input);
+        let mut new_premises = vec![old_derivation];
+        new_premises.extend(premises.into_iter().map(Derivation::Clause));
+        self.derivation = Derivation::Inference(rule, new_premises);
+    }
 }
 
 impl Index<usize> for Clause {
@@ -0,0 +1,119 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+/// The rules a clause can be derived with.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[allow(missing_docs)]
+pub enum InferenceRule {
+    Superposition,
+    EqualityFactoring,
+    EqualityResolution,
+    Rewriting,
+    SimplifyReflect,
+    Evaluation,
+    DistinctObjects,
+}
+
+impl InferenceRule {
+    /// Get the name of the rule used in TSTP inference records.
+    pub fn tstp_name(&self) -> &'static str {
+        match *self {
+            InferenceRule::Superposition => "superposition",
+            InferenceRule::EqualityFactoring => "equality_factoring",
+            InferenceRule::EqualityResolution => "equality_resolution",
+            InferenceRule::Rewriting => "rewriting",
+            InferenceRule::SimplifyReflect => "simplify_reflect",
+            InferenceRule::Evaluation => "evaluation",
+            InferenceRule::DistinctObjects => "distinct_objects",
+        }
+    }
+}
+
+/// Describes how a clause was derived.
+/// Simplifications are done in place, so they wrap the derivation of the simplified clause.
+/// Since the clause keeps its ID, an input derivation wrapped this way refers to the input version of the clause.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Derivation {
+    /// The clause comes straight from the input problem.
+    Input,
+    /// The clause with the given ID.
+    Clause(u64),
+    /// The clause was derived from the premises with the given rule.
+    Inference(InferenceRule, Vec<Derivation>),
+}
+
+impl Derivation {
+    /// Creates a new derivation for a generating inference between the clauses with the given IDs.
+    /// An inference between a clause and itself only mentions the clause once.
+    pub fn new_inference(rule: InferenceRule, parents: &[u64]) -> Derivation {
+        let mut ids = parents.to_vec();
+        ids.dedup();
+        Derivation::Inference(rule, ids.into_iter().map(Derivation::Clause).collect())
+    }
+
+    /// Checks if the derivation starts from the input version of the clause.
+    pub fn has_input(&self) -> bool {
+        match *self {
+            Derivation::Input => true,
+            Derivation::Clause(_) => false,
+            Derivation::Inference(_, ref premises) => premises.iter().any(|p| p.has_input()),
+        }
+    }
+
+    /// Collects the IDs of all clauses this derivation refers to.
+    pub fn parent_ids(&self, ids: &mut Vec<u64>) {
+        match *self {
+            Derivation::Input => {}
+            Derivation::Clause(id) => ids.push(id),
+            Derivation::Inference(_, ref premises) => {
+                for p in premises {
+                    p.parent_ids(ids);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Derivation, InferenceRule};
+
+    #[test]
+    fn parent_ids_1() {
+        let sp = Derivation::new_inference(InferenceRule::Superposition, &[3, 5]);
+        let rw = Derivation::Inference(
+            InferenceRule::Rewriting,
+            vec![sp, Derivation::Clause(7), Derivation::Input],
+        );
+        let mut ids = Vec::new();
+        rw.parent_ids(&mut ids);
+
+        assert_eq!(ids, vec![3, 5, 7]);
+    }
+
+    #[test]
+    fn has_input_1() {
+        let sp = Derivation::new_inference(InferenceRule::Superposition, &[3, 5]);
+        assert!(!sp.has_input());
+        assert!(Derivation::Input.has_input());
+
+        let rw = Derivation::Inference(
+            InferenceRule::Rewriting,
+            vec![Derivation::Input, Derivation::Clause(7)],
+        );
+        assert!(rw.has_input());
+    }
+}
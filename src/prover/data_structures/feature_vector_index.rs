@@ -0,0 +1,261 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::HashSet;
+use std::collections::BTreeMap;
+
+/// The amount of buckets function symbols are counted in, for both positive and negative literals.
+const SYMBOL_BUCKETS: usize = 4;
+
+/// Adds the function symbols of a term to the given buckets.
+fn count_symbols(t: &Term, buckets: &mut [usize]) {
+    if t.is_function() {
+        buckets[t.get_id() as usize % SYMBOL_BUCKETS] += 1;
+        for sub_t in t.iter() {
+            count_symbols(sub_t, buckets);
+        }
+    }
+}
+
+/// Calculates the feature vector of a clause.
+/// The features are the amount of positive and negative literals,
+/// the maximum term depth in positive and negative literals,
+/// and the amount of function symbols in positive and negative literals divided into buckets.
+/// If a clause subsumes another clause, each of its features is at most that of the other clause.
+fn feature_vector(cl: &Clause) -> Vec<usize> {
+    let mut features = vec![0; 4 + 2 * SYMBOL_BUCKETS];
+
+    for l in cl.iter() {
+        let (count, rest) = if l.is_positive() {
+            (0, 4)
+        } else {
+            (1, 4 + SYMBOL_BUCKETS)
+        };
+        features[count] += 1;
+        features[count + 2] = features[count + 2]
+            .max(l.get_lhs().get_depth())
+            .max(l.get_rhs().get_depth());
+        count_symbols(l.get_lhs(), &mut features[rest..rest + SYMBOL_BUCKETS]);
+        count_symbols(l.get_rhs(), &mut features[rest..rest + SYMBOL_BUCKETS]);
+    }
+
+    features
+}
+
+/// A feature vector index is used for fast retrieval of the clauses which might subsume a query clause
+/// and the clauses which the query clause might subsume.
+/// The clauses are stored in a trie according to their feature vectors.
+/// The index only stores the IDs of the clauses.
+#[derive(Debug, Clone)]
+pub enum FeatureVectorIndex {
+    Leaf(Vec<u64>),
+    Node(BTreeMap<usize, FeatureVectorIndex>),
+}
+
+impl Default for FeatureVectorIndex {
+    fn default() -> FeatureVectorIndex {
+        FeatureVectorIndex::new()
+    }
+}
+
+impl FeatureVectorIndex {
+    /// Creates an empty feature vector index.
+    pub fn new() -> FeatureVectorIndex {
+        FeatureVectorIndex::Node(BTreeMap::new())
+    }
+
+    /// Adds a clause to the index.
+    pub fn add_clause(&mut self, cl: &Clause) {
+        let fv = feature_vector(cl);
+        let mut current = self;
+
+        for (i, f) in fv.iter().enumerate() {
+            current = match *current {
+                FeatureVectorIndex::Node(ref mut m) => m.entry(*f).or_insert_with(|| {
+                    if i + 1 < fv.len() {
+                        FeatureVectorIndex::new()
+                    } else {
+                        FeatureVectorIndex::Leaf(Vec::new())
+                    }
+                }),
+                FeatureVectorIndex::Leaf(_) => unreachable!(),
+            };
+        }
+
+        if let FeatureVectorIndex::Leaf(ref mut v) = *current {
+            v.push(cl.get_id());
+        }
+    }
+
+    /// Removes a clause from the index.
+    /// Paths which become empty are removed from the index.
+    pub fn remove_clause(&mut self, cl: &Clause) {
+        let fv = feature_vector(cl);
+        let id = cl.get_id();
+
+        // First find the leaf, and the amount of children of each node on the way there.
+        let mut child_counts = Vec::with_capacity(fv.len());
+        let mut current = &*self;
+        for f in &fv {
+            match *current {
+                FeatureVectorIndex::Node(ref m) => {
+                    child_counts.push(m.len());
+                    match m.get(f) {
+                        Some(subtree) => current = subtree,
+                        None => return,
+                    }
+                }
+                FeatureVectorIndex::Leaf(_) => return,
+            }
+        }
+        let leaf_emptied = match *current {
+            FeatureVectorIndex::Leaf(ref v) => v.iter().all(|&x| x == id),
+            FeatureVectorIndex::Node(_) => return,
+        };
+
+        // If the leaf becomes empty, the path is cut below the deepest node with other children.
+        // The root is never removed.
+        let depth = if leaf_emptied {
+            let mut j = fv.len() - 1;
+            while j > 0 && child_counts[j] == 1 {
+                j -= 1;
+            }
+            j
+        } else {
+            fv.len()
+        };
+
+        let mut current = self;
+        for f in &fv[..depth] {
+            current = match *current {
+                FeatureVectorIndex::Node(ref mut m) => {
+                    m.get_mut(f).expect("The path was just found")
+                }
+                FeatureVectorIndex::Leaf(_) => unreachable!(),
+            };
+        }
+        match *current {
+            FeatureVectorIndex::Node(ref mut m) => {
+                m.remove(&fv[depth]);
+            }
+            FeatureVectorIndex::Leaf(ref mut v) => v.retain(|&x| x != id),
+        }
+    }
+
+    /// Checks if there is nothing stored in the index.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            FeatureVectorIndex::Leaf(ref v) => v.is_empty(),
+            FeatureVectorIndex::Node(ref m) => m.is_empty(),
+        }
+    }
+
+    /// Adds the IDs of the clauses which might subsume the given clause to `ids`.
+    pub fn possible_subsumers(&self, cl: &Clause, ids: &mut HashSet<u64>) {
+        self.query(&feature_vector(cl), true, ids);
+    }
+
+    /// Adds the IDs of the clauses which the given clause might subsume to `ids`.
+    pub fn possibly_subsumed(&self, cl: &Clause, ids: &mut HashSet<u64>) {
+        self.query(&feature_vector(cl), false, ids);
+    }
+
+    /// Helper for above. Finds the feature vectors which are pointwise at most (or at least) the given one.
+    /// The depth of the recursion is bounded by the length of the feature vector.
+    fn query(&self, fv: &[usize], at_most: bool, ids: &mut HashSet<u64>) {
+        match *self {
+            FeatureVectorIndex::Leaf(ref v) => ids.extend(v.iter().cloned()),
+            FeatureVectorIndex::Node(ref m) => {
+                let subtrees = if at_most {
+                    m.range(..=fv[0])
+                } else {
+                    m.range(fv[0]..)
+                };
+                for (_, subtree) in subtrees {
+                    subtree.query(&fv[1..], at_most, ids);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FeatureVectorIndex;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::utils::hash_map::HashSet;
+
+    #[test]
+    fn feature_vector_index_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let f_a = Term::new_function(2, vec![a.clone()]);
+        let t = Term::new_truth();
+
+        // P(x) | ~Q(x)
+        let mut cl1 = Clause::new(vec![
+            Literal::new(false, Term::new_function(3, vec![x.clone()]), t.clone()),
+            Literal::new(true, Term::new_function(4, vec![x.clone()]), t.clone()),
+        ]);
+        // P(f(a)) | ~Q(f(a)) | a = f(a)
+        let mut cl2 = Clause::new(vec![
+            Literal::new(false, Term::new_function(3, vec![f_a.clone()]), t.clone()),
+            Literal::new(true, Term::new_function(4, vec![f_a.clone()]), t.clone()),
+            Literal::new(false, a.clone(), f_a),
+        ]);
+        // P(x) | P(f(x))
+        let mut cl3 = Clause::new(vec![
+            Literal::new(false, Term::new_function(3, vec![x]), t.clone()),
+            Literal::new(false, Term::new_function(3, vec![f_x]), t),
+        ]);
+        cl1.set_id(0);
+        cl2.set_id(1);
+        cl3.set_id(2);
+
+        let mut index = FeatureVectorIndex::new();
+        index.add_clause(&cl1);
+        index.add_clause(&cl2);
+        index.add_clause(&cl3);
+
+        let mut ids = HashSet::default();
+        index.possible_subsumers(&cl2, &mut ids);
+        assert!(ids.contains(&0) && ids.contains(&1));
+        assert!(!ids.contains(&2));
+
+        ids.clear();
+        index.possibly_subsumed(&cl1, &mut ids);
+        assert!(ids.contains(&0) && ids.contains(&1));
+        assert!(!ids.contains(&2));
+
+        ids.clear();
+        index.remove_clause(&cl1);
+        index.possible_subsumers(&cl2, &mut ids);
+        assert!(!ids.contains(&0));
+
+        ids.clear();
+        index.remove_clause(&cl2);
+        index.possibly_subsumed(&cl1, &mut ids);
+        assert!(ids.is_empty());
+
+        index.remove_clause(&cl3);
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::{HashMap, HashSet};
+
+/// The positions of a term which are sampled for its fingerprint.
+const SAMPLE_POSITIONS: [&[usize]; 7] = [&[], &[0], &[1], &[0, 0], &[0, 1], &[1, 0], &[1, 1]];
+
+/// What can be found at a position of a term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Symbol(i64),
+    Variable,
+    BelowVariable,
+    NonExistent,
+}
+
+impl Feature {
+    /// Checks if two terms with these features at the same position can be unifiable.
+    fn compatible(self, other: Feature) -> bool {
+        match (self, other) {
+            (Feature::BelowVariable, _) | (_, Feature::BelowVariable) => true,
+            (Feature::Symbol(f), Feature::Symbol(g)) => f == g,
+            (Feature::NonExistent, f) | (f, Feature::NonExistent) => f == Feature::NonExistent,
+            _ => true,
+        }
+    }
+}
+
+/// Get the feature of a term at the given position.
+fn feature_at(t: &Term, pos: &[usize]) -> Feature {
+    let mut current = t;
+    for &i in pos {
+        if current.is_variable() {
+            return Feature::BelowVariable;
+        } else if i >= current.get_arity() {
+            return Feature::NonExistent;
+        }
+        current = &current[i];
+    }
+
+    if current.is_variable() {
+        Feature::Variable
+    } else {
+        Feature::Symbol(current.get_id())
+    }
+}
+
+/// Computes the fingerprint of a term.
+fn fingerprint(t: &Term) -> Vec<Feature> {
+    SAMPLE_POSITIONS
+        .iter()
+        .map(|pos| feature_at(t, pos))
+        .collect()
+}
+
+/// A fingerprint index is used for fast retrieval of the terms which might be unifiable with a query term.
+/// Each term is represented by the symbols found at a few fixed positions.
+/// Two terms whose fingerprints are incompatible at some position cannot be unifiable.
+/// The index only stores the ID of the clause each term comes from.
+#[derive(Debug, Clone)]
+pub enum FingerprintIndex {
+    Leaf(Vec<u64>),
+    Node(HashMap<Feature, FingerprintIndex>),
+}
+
+impl Default for FingerprintIndex {
+    fn default() -> FingerprintIndex {
+        FingerprintIndex::new()
+    }
+}
+
+impl FingerprintIndex {
+    /// Creates an empty fingerprint index.
+    pub fn new() -> FingerprintIndex {
+        FingerprintIndex::Node(HashMap::default())
+    }
+
+    /// Adds a term to the index.
+    /// The ID is the ID of the clause the term comes from.
+    pub fn add_term(&mut self, t: &Term, id: u64) {
+        let fp = fingerprint(t);
+        let mut current = self;
+
+        for (i, f) in fp.iter().enumerate() {
+            current = match *current {
+                FingerprintIndex::Node(ref mut m) => m.entry(*f).or_insert_with(|| {
+                    if i + 1 < fp.len() {
+                        FingerprintIndex::new()
+                    } else {
+                        FingerprintIndex::Leaf(Vec::new())
+                    }
+                }),
+                FingerprintIndex::Leaf(_) => unreachable!(),
+            };
+        }
+
+        if let FingerprintIndex::Leaf(ref mut v) = *current {
+            // The terms of a clause are added one after another so this is enough to avoid most duplicates.
+            if v.last() != Some(&id) {
+                v.push(id);
+            }
+        }
+    }
+
+    /// Removes a term coming from the clause with the given ID from the index.
+    /// Paths which become empty are removed from the index.
+    pub fn remove_term(&mut self, t: &Term, id: u64) {
+        let fp = fingerprint(t);
+
+        // First find the leaf, and the amount of children of each node on the way there.
+        let mut child_counts = Vec::with_capacity(fp.len());
+        let mut current = &*self;
+        for f in &fp {
+            match *current {
+                FingerprintIndex::Node(ref m) => {
+                    child_counts.push(m.len());
+                    match m.get(f) {
+                        Some(subtree) => current = subtree,
+                        None => return,
+                    }
+                }
+                FingerprintIndex::Leaf(_) => return,
+            }
+        }
+        let leaf_emptied = match *current {
+            FingerprintIndex::Leaf(ref v) => v.iter().all(|&x| x == id),
+            FingerprintIndex::Node(_) => return,
+        };
+
+        // If the leaf becomes empty, the path is cut below the deepest node with other children.
+        // The root is never removed.
+        let depth = if leaf_emptied {
+            let mut j = fp.len() - 1;
+            while j > 0 && child_counts[j] == 1 {
+                j -= 1;
+            }
+            j
+        } else {
+            fp.len()
+        };
+
+        let mut current = self;
+        for f in &fp[..depth] {
+            current = match *current {
+                FingerprintIndex::Node(ref mut m) => m.get_mut(f).expect("The path was just found"),
+                FingerprintIndex::Leaf(_) => unreachable!(),
+            };
+        }
+        match *current {
+            FingerprintIndex::Node(ref mut m) => {
+                m.remove(&fp[depth]);
+            }
+            FingerprintIndex::Leaf(ref mut v) => v.retain(|&x| x != id),
+        }
+    }
+
+    /// Checks if there is nothing stored in the index.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            FingerprintIndex::Leaf(ref v) => v.is_empty(),
+            FingerprintIndex::Node(ref m) => m.is_empty(),
+        }
+    }
+
+    /// Adds the IDs of the clauses containing terms possibly unifiable with the given term to `ids`.
+    pub fn unifiable_ids(&self, t: &Term, ids: &mut HashSet<u64>) {
+        self.unifiable_ids_helper(&fingerprint(t), ids);
+    }
+
+    /// Helper for above. The depth of the recursion is bounded by the length of the fingerprint.
+    fn unifiable_ids_helper(&self, fp: &[Feature], ids: &mut HashSet<u64>) {
+        match *self {
+            FingerprintIndex::Leaf(ref v) => ids.extend(v.iter().cloned()),
+            FingerprintIndex::Node(ref m) => match fp[0] {
+                // Only a few features can be compatible with these so we can look them up directly.
+                Feature::Symbol(_) | Feature::NonExistent => {
+                    for f in &[fp[0], Feature::Variable, Feature::BelowVariable] {
+                        if fp[0].compatible(*f) {
+                            if let Some(subtree) = m.get(f) {
+                                subtree.unifiable_ids_helper(&fp[1..], ids);
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    for (f, subtree) in m.iter() {
+                        if fp[0].compatible(*f) {
+                            subtree.unifiable_ids_helper(&fp[1..], ids);
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{feature_at, Feature, FingerprintIndex};
+    use crate::prover::data_structures::term::Term;
+    use crate::utils::hash_map::HashSet;
+
+    #[test]
+    fn feature_at_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x_a = Term::new_function(2, vec![x, a]);
+
+        assert_eq!(feature_at(&f_x_a, &[]), Feature::Symbol(2));
+        assert_eq!(feature_at(&f_x_a, &[0]), Feature::Variable);
+        assert_eq!(feature_at(&f_x_a, &[1]), Feature::Symbol(1));
+        assert_eq!(feature_at(&f_x_a, &[0, 1]), Feature::BelowVariable);
+        assert_eq!(feature_at(&f_x_a, &[1, 0]), Feature::NonExistent);
+    }
+
+    #[test]
+    fn fingerprint_index_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let b = Term::new_constant(2);
+        let f_a = Term::new_function(3, vec![a.clone()]);
+        let f_b = Term::new_function(3, vec![b]);
+        let f_x = Term::new_function(3, vec![x.clone()]);
+        let g_a = Term::new_function(4, vec![a]);
+
+        let mut index = FingerprintIndex::new();
+        index.add_term(&f_a, 0);
+        index.add_term(&f_b, 1);
+        index.add_term(&g_a, 2);
+        index.add_term(&x, 3);
+
+        let mut ids = HashSet::default();
+        index.unifiable_ids(&f_x, &mut ids);
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.contains(&2));
+
+        ids.clear();
+        index.unifiable_ids(&f_a, &mut ids);
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&0) && ids.contains(&3));
+
+        ids.clear();
+        index.remove_term(&f_a, 0);
+        index.unifiable_ids(&f_x, &mut ids);
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&0));
+    }
+
+    #[test]
+    fn fingerprint_index_remove_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_a = Term::new_function(2, vec![a.clone()]);
+        let f_x = Term::new_function(2, vec![x]);
+        let g_a_a = Term::new_function(3, vec![a.clone(), a]);
+
+        let mut index = FingerprintIndex::new();
+        index.add_term(&f_a, 0);
+        index.add_term(&f_x, 0);
+        index.add_term(&f_x, 1);
+        index.add_term(&g_a_a, 2);
+
+        index.remove_term(&g_a_a, 2);
+        index.remove_term(&f_a, 0);
+        index.remove_term(&f_x, 0);
+        let mut ids = HashSet::default();
+        index.unifiable_ids(&f_a, &mut ids);
+        assert_eq!(ids.len(), 1);
+        assert!(ids.contains(&1));
+
+        index.remove_term(&f_x, 1);
+        assert!(index.is_empty());
+    }
+}
@@ -32,11 +32,7 @@ pub struct Literal {
 impl Literal {
     /// Creates a new literal.
     pub fn new(negated: bool, lhs: Term, rhs: Term) -> Literal {
-        Literal {
-            lhs: lhs,
-            rhs: rhs,
-            negated: negated,
-        }
+        Literal { lhs, rhs, negated }
     }
 
     /// Checks if the literal is positive.
@@ -92,7 +88,7 @@ impl Literal {
     }
 
     /// Used for iterating through the lhs and rhs of the literal.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             literal: self,
             index: 0,
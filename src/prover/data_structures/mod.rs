@@ -23,5 +23,17 @@ pub mod literal;
 /// Contains the data structure for clauses.
 pub mod clause;
 
+/// Contains the data structure for keeping track of how clauses were derived.
+pub mod derivation;
+
 /// Contains an implementation of a perfect discrimination tree.
 pub mod pd_tree;
+
+/// Contains an implementation of a fingerprint index.
+pub mod fingerprint_index;
+
+/// Contains an implementation of a feature vector index.
+pub mod feature_vector_index;
+
+/// Contains a bank for storing terms with shared subterms.
+pub mod term_bank;
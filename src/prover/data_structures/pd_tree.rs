@@ -19,8 +19,9 @@
 
 use crate::prover::data_structures::clause::Clause;
 use crate::prover::data_structures::term::Term;
-use crate::prover::ordering::term_ordering::TermOrdering;
-use crate::prover::unification::matching::term_match_with_subst;
+use crate::prover::ordering::term_ordering::{Comparison, TermOrdering};
+use crate::prover::unification::full_unification::mgu;
+use crate::prover::unification::matching::{term_match, term_match_with_subst};
 use crate::prover::unification::substitution::Substitution;
 use crate::utils::either::Either;
 use crate::utils::hash_map::HashMap;
@@ -48,7 +49,7 @@ impl<'a> PrefixOrderIterator<'a> {
 
     /// Implementing peek by hand like this is a lot easier than using Peekable.
     fn peek(&mut self) -> Option<&'a Term> {
-        if !self.peek_term.is_some() {
+        if self.peek_term.is_none() {
             self.peek_term = self.next();
         }
         self.peek_term
@@ -103,10 +104,18 @@ fn normalize_variables(l: &mut Term, r: &mut Term) {
 /// generalizations (an equation where one side matches the query term)
 /// and specializations (an equation where the query term matches one side)
 /// of equations. This is critical for efficient use of many inference and simplification rules.
+/// The children of a node are keyed by the ID, sort and arity of the symbol at that position.
+/// The arities are needed for skipping whole terms in the tree.
 #[derive(Debug, Clone)]
 pub enum PDTree {
-    Leaf(Vec<(Term, Term, bool, bool)>),
-    Node(HashMap<i64, PDTree>),
+    Leaf(Vec<(Term, Term, bool, bool, u64)>),
+    Node(HashMap<(i64, i64, usize), PDTree>),
+}
+
+impl Default for PDTree {
+    fn default() -> PDTree {
+        PDTree::new()
+    }
 }
 
 impl PDTree {
@@ -119,27 +128,112 @@ impl PDTree {
     /// Does nothing if the clause is not unit.
     pub fn add_clause_to_index(&mut self, term_ordering: &TermOrdering, cl: &Clause) {
         if cl.is_unit() {
+            let id = cl.get_id();
             let lit = &cl[0];
             let pos = lit.is_positive();
             let l = &lit.get_lhs();
             let r = &lit.get_rhs();
-            if term_ordering.gt(l, r) {
-                self.add_eq_to_index(l, r, pos, true);
-            } else if term_ordering.gt(r, l) {
-                self.add_eq_to_index(r, l, pos, true);
-            } else {
-                self.add_eq_to_index(l, r, pos, false);
-                self.add_eq_to_index(r, l, pos, false);
+            match term_ordering.compare(l, r) {
+                Comparison::Greater => self.add_eq_to_index(l, r, pos, true, id),
+                Comparison::Less => self.add_eq_to_index(r, l, pos, true, id),
+                _ => {
+                    self.add_eq_to_index(l, r, pos, false, id);
+                    self.add_eq_to_index(r, l, pos, false, id);
+                }
             }
         }
     }
 
     /// Adds an equation to the index without taking into account symmetry.
-    pub fn add_eq_to_index(&mut self, s: &Term, t: &Term, pos: bool, oriented: bool) {
+    /// The ID is the ID of the clause the equation comes from.
+    pub fn add_eq_to_index(&mut self, s: &Term, t: &Term, pos: bool, oriented: bool, id: u64) {
         let mut s_n = s.clone();
         let mut t_n = t.clone();
         normalize_variables(&mut s_n, &mut t_n);
-        self.insert_at_leaf(PrefixOrderIterator::new(&s_n), &s_n, t_n, pos, oriented);
+        self.insert_at_leaf(PrefixOrderIterator::new(&s_n), &s_n, t_n, pos, oriented, id);
+    }
+
+    /// Removes a clause from the index.
+    /// Does nothing if the clause is not unit.
+    pub fn remove_clause_from_index(&mut self, term_ordering: &TermOrdering, cl: &Clause) {
+        if cl.is_unit() {
+            let id = cl.get_id();
+            let l = &cl[0].get_lhs();
+            let r = &cl[0].get_rhs();
+            match term_ordering.compare(l, r) {
+                Comparison::Greater => self.remove_eq_from_index(l, id),
+                Comparison::Less => self.remove_eq_from_index(r, id),
+                _ => {
+                    self.remove_eq_from_index(l, id);
+                    self.remove_eq_from_index(r, id);
+                }
+            }
+        }
+    }
+
+    /// Removes the equations with the given left hand side coming from the clause with the given ID.
+    /// Paths which become empty are removed from the tree.
+    /// Implemented iteratively for the same reason as insertion.
+    pub fn remove_eq_from_index(&mut self, s: &Term, id: u64) {
+        let mut s_n = s.clone();
+        normalize_variables(&mut s_n, &mut Term::new_truth());
+        let keys = PrefixOrderIterator::new(&s_n)
+            .map(symbol_key)
+            .collect::<Vec<_>>();
+
+        // First find the leaf, and the amount of children of each node on the way there.
+        let mut child_counts = Vec::with_capacity(keys.len());
+        let mut current = &*self;
+        for key in &keys {
+            match *current {
+                PDTree::Node(ref m) => {
+                    child_counts.push(m.len());
+                    match m.get(key) {
+                        Some(subtree) => current = subtree,
+                        None => return,
+                    }
+                }
+                PDTree::Leaf(_) => return,
+            }
+        }
+        let leaf_emptied = match *current {
+            PDTree::Leaf(ref v) => v.iter().all(|&(_, _, _, _, eq_id)| eq_id == id),
+            PDTree::Node(_) => return,
+        };
+
+        // If the leaf becomes empty, the path is cut below the deepest node with other children.
+        // The root is never removed.
+        let depth = if leaf_emptied {
+            let mut j = keys.len() - 1;
+            while j > 0 && child_counts[j] == 1 {
+                j -= 1;
+            }
+            j
+        } else {
+            keys.len()
+        };
+
+        let mut current = self;
+        for key in &keys[..depth] {
+            current = match *current {
+                PDTree::Node(ref mut m) => m.get_mut(key).expect("The path was just found"),
+                PDTree::Leaf(_) => unreachable!(),
+            };
+        }
+        match *current {
+            PDTree::Node(ref mut m) => {
+                m.remove(&keys[depth]);
+            }
+            PDTree::Leaf(ref mut v) => v.retain(|&(_, _, _, _, eq_id)| eq_id != id),
+        }
+    }
+
+    /// Checks if there is nothing stored in the tree.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            PDTree::Leaf(ref v) => v.is_empty(),
+            PDTree::Node(ref m) => m.is_empty(),
+        }
     }
 
     /// Inserts l = r (or l <> r) into the tree, constructing the path if it doesn't exist.
@@ -152,12 +246,13 @@ impl PDTree {
         r: Term,
         sign: bool,
         oriented: bool,
+        id: u64,
     ) {
         let mut current = Some(self);
 
         while let Some(t) = iter.next() {
             current = current.take().and_then(|tree| match *tree {
-                PDTree::Node(ref mut m) => Some(m.entry(t.get_id()).or_insert_with(|| {
+                PDTree::Node(ref mut m) => Some(m.entry(symbol_key(t)).or_insert_with(|| {
                     if iter.peek().is_some() {
                         PDTree::new()
                     } else {
@@ -172,10 +267,8 @@ impl PDTree {
             }
         }
 
-        if let Some(tree) = current {
-            if let PDTree::Leaf(ref mut v) = *tree {
-                v.push((l.clone(), r, sign, oriented));
-            }
+        if let Some(PDTree::Leaf(ref mut v)) = current {
+            v.push((l.clone(), r, sign, oriented, id));
         }
     }
 
@@ -187,6 +280,114 @@ impl PDTree {
     ) -> GeneralizationIterator<'a> {
         GeneralizationIterator::new(self, t, sign)
     }
+
+    /// Finds specializations of a given term with the given sign.
+    /// The substitution returned maps the given term to the left hand side of the equation.
+    pub fn iter_specializations<'a>(
+        &'a self,
+        t: &Term,
+        sign: bool,
+    ) -> impl Iterator<Item = (&'a Term, &'a Term, Substitution, bool, u64)> {
+        let t = t.clone();
+        self.candidates(&t, false)
+            .into_iter()
+            .filter(move |&&(_, _, s, _, _)| s == sign)
+            .filter_map(move |&(ref l, ref r, _, oriented, id)| {
+                term_match(&t, l).map(|sigma| (l, r, sigma, oriented, id))
+            })
+    }
+
+    /// Finds equations with the given sign where the left hand side is unifiable with a given term.
+    /// The equations are renamed so that they have no variables in common with the given term.
+    /// The substitution returned is the most general unifier of the term and the left hand side.
+    pub fn iter_unifiable<'a>(
+        &'a self,
+        t: &Term,
+        sign: bool,
+    ) -> impl Iterator<Item = (Term, Term, Substitution, bool, u64)> + 'a {
+        let t = t.clone();
+        let var_cnt = t.min_variable_id();
+        self.candidates(&t, true)
+            .into_iter()
+            .filter(move |&&(_, _, s, _, _)| s == sign)
+            .filter_map(move |&(ref l, ref r, _, oriented, id)| {
+                let mut l_r = l.clone();
+                let mut r_r = r.clone();
+                let mut m = HashMap::default();
+                let mut x = var_cnt;
+                l_r.rename_no_common(&mut m, &mut x);
+                r_r.rename_no_common(&mut m, &mut x);
+                mgu(&t, &l_r).map(|sigma| (l_r, r_r, sigma, oriented, id))
+            })
+    }
+
+    /// Collects the equations where the left hand side might be an instance of the given term.
+    /// If `unify` is true, variables in the tree can also be bound to subterms of the given term.
+    /// The variables are not bound while traversing the tree, so the candidates still need to be checked.
+    fn candidates<'a>(&'a self, t: &Term, unify: bool) -> Vec<&'a (Term, Term, bool, bool, u64)> {
+        let mut candidates = Vec::new();
+        let mut skipped = Vec::new();
+        let mut stack = vec![(self, PrefixOrderIterator::new(t))];
+
+        while let Some((tree, mut iter)) = stack.pop() {
+            match (tree, iter.next()) {
+                (PDTree::Leaf(v), None) => candidates.extend(v.iter()),
+                (PDTree::Node(m), Some(q)) => {
+                    if q.is_variable() {
+                        // A variable can be bound to any term, so one whole term is skipped in the tree.
+                        tree.skip_term(q.get_sort(), &mut skipped);
+                        stack.extend(skipped.drain(..).map(|subtree| (subtree, iter.clone())));
+                        continue;
+                    }
+
+                    if unify {
+                        // A variable in the tree can be bound to the whole subterm.
+                        for (&(k, sort, _), subtree) in m.iter() {
+                            if k < 0 && sort == q.get_sort() {
+                                let mut new_iter = iter.clone();
+                                new_iter.skip_subtree();
+                                stack.push((subtree, new_iter));
+                            }
+                        }
+                    }
+                    if let Some(subtree) = m.get(&symbol_key(q)) {
+                        stack.push((subtree, iter));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        candidates
+    }
+
+    /// Adds the points of the tree reached after skipping one whole term of the given sort to `subtrees`.
+    fn skip_term<'a>(&'a self, sort: i64, subtrees: &mut Vec<&'a PDTree>) {
+        if let PDTree::Node(ref m) = *self {
+            // The amount of terms still to skip grows by the arity of each symbol passed.
+            // Only the top symbol of the skipped term needs to have the right sort.
+            let mut stack = m
+                .iter()
+                .filter(|&(&(_, s, _), _)| s == sort)
+                .map(|(&(_, _, arity), subtree)| (subtree, arity))
+                .collect::<Vec<_>>();
+
+            while let Some((tree, remaining)) = stack.pop() {
+                if remaining == 0 {
+                    subtrees.push(tree);
+                } else if let PDTree::Node(ref m) = *tree {
+                    for (&(_, _, arity), subtree) in m.iter() {
+                        stack.push((subtree, remaining - 1 + arity));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The key of the position of a term in the tree.
+fn symbol_key(t: &Term) -> (i64, i64, usize) {
+    (t.get_id(), t.get_sort(), t.get_arity())
 }
 
 pub struct GeneralizationIterator<'a> {
@@ -195,7 +396,8 @@ pub struct GeneralizationIterator<'a> {
 }
 
 /// Either an iterator of the children of an node, or the stuff at a leaf node.
-type LevelIter<'a> = Either<MIter<'a, i64, PDTree>, VIter<'a, (Term, Term, bool, bool)>>;
+type LevelIter<'a> =
+    Either<MIter<'a, (i64, i64, usize), PDTree>, VIter<'a, (Term, Term, bool, bool, u64)>>;
 
 struct StackFrame<'a> {
     subst: Substitution,
@@ -207,7 +409,7 @@ impl<'a> GeneralizationIterator<'a> {
     fn new(pd_tree: &'a PDTree, t: &'a Term, sign: bool) -> GeneralizationIterator<'a> {
         if let PDTree::Node(ref m) = *pd_tree {
             GeneralizationIterator {
-                sign: sign,
+                sign,
                 stack: vec![StackFrame {
                     subst: Substitution::new(),
                     iter: PrefixOrderIterator::new(t),
@@ -221,7 +423,7 @@ impl<'a> GeneralizationIterator<'a> {
 }
 
 impl<'a> Iterator for GeneralizationIterator<'a> {
-    type Item = (&'a Term, &'a Term, Substitution, bool);
+    type Item = (&'a Term, &'a Term, Substitution, bool, u64);
 
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(StackFrame {
@@ -235,7 +437,7 @@ impl<'a> Iterator for GeneralizationIterator<'a> {
                     if let Some(t) = iter.peek() {
                         let id = t.get_id();
                         // Cannot use a for-loop as that moves the iterator.
-                        while let Some((&k, subtree)) = subtree_iter.next() {
+                        while let Some((&(k, sort, _), subtree)) = subtree_iter.next() {
                             // If the function symbols are the same we can skip it.
                             if k == id && k >= 0 {
                                 let mut new_iter = iter.clone();
@@ -247,21 +449,23 @@ impl<'a> Iterator for GeneralizationIterator<'a> {
                                 // Remember to push the stack frame back as we are not done with this node.
                                 self.stack.push(StackFrame {
                                     subst: subst.clone(),
-                                    iter: iter,
+                                    iter,
                                     tree_iter: Either::Left(subtree_iter),
                                 });
                                 // Now add the stack frame for the recursive call.
                                 self.stack.push(StackFrame {
-                                    subst: subst,
+                                    subst,
                                     iter: new_iter,
                                     tree_iter: new_tree_iter,
                                 });
                                 break;
                             } else if k < 0 {
                                 // Otherwise check if we can bind and continue.
-                                if let Some(new_subst) =
-                                    term_match_with_subst(subst.clone(), &Term::new_variable(k), t)
-                                {
+                                if let Some(new_subst) = term_match_with_subst(
+                                    subst.clone(),
+                                    &Term::new_sorted_variable(k, sort),
+                                    t,
+                                ) {
                                     let mut new_iter = iter.clone();
                                     new_iter.next(); // Again, need to advance once
                                     new_iter.skip_subtree(); // Since we matched we can skip a subtree.
@@ -271,8 +475,8 @@ impl<'a> Iterator for GeneralizationIterator<'a> {
                                     };
                                     // Remember to push the stack frame back as we are not done with this node.
                                     self.stack.push(StackFrame {
-                                        subst: subst,
-                                        iter: iter,
+                                        subst,
+                                        iter,
                                         tree_iter: Either::Left(subtree_iter),
                                     });
                                     // Now add the stack frame for the recursive call.
@@ -290,16 +494,16 @@ impl<'a> Iterator for GeneralizationIterator<'a> {
                 Either::Right(mut leaf_iter) => {
                     if iter.next().is_none() {
                         // Cannot use a for-loop as that moves the iterator.
-                        while let Some(&(ref l, ref r, sign, oriented)) = leaf_iter.next() {
+                        while let Some(&(ref l, ref r, sign, oriented, id)) = leaf_iter.next() {
                             if self.sign == sign {
                                 let subst_copy = subst.clone();
                                 // Remember to push the stack frame back as we are not done with this leaf.
                                 self.stack.push(StackFrame {
-                                    subst: subst,
-                                    iter: iter,
+                                    subst,
+                                    iter,
                                     tree_iter: Either::Right(leaf_iter),
                                 });
-                                return Some((l, r, subst_copy, oriented));
+                                return Some((l, r, subst_copy, oriented, id));
                             }
                         }
                     }
@@ -316,6 +520,189 @@ mod test {
     use super::PDTree;
     use super::PrefixOrderIterator;
     use crate::prover::data_structures::term::Term;
+    use crate::prover::unification::full_unification::mgu;
+    use crate::prover::unification::matching::term_match;
+    use crate::utils::hash_map::HashMap;
+
+    /// A simple xorshift generator so that the randomized tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    /// Generates a random term over the constants a and b, the functions f/1 and g/2,
+    /// and the variables x, y and z.
+    fn random_term(rng: &mut Rng, depth: usize) -> Term {
+        let choice = if depth == 0 { rng.next(5) } else { rng.next(7) };
+        match choice {
+            0 => Term::new_constant(1),
+            1 => Term::new_constant(2),
+            2..=4 => Term::new_variable(-(choice as i64) + 1),
+            5 => Term::new_function(3, vec![random_term(rng, depth - 1)]),
+            _ => Term::new_function(
+                4,
+                vec![random_term(rng, depth - 1), random_term(rng, depth - 1)],
+            ),
+        }
+    }
+
+    /// Renames the variables of a term so that they are disjoint from those of the random terms.
+    fn rename_apart(t: &Term) -> Term {
+        let mut t = t.clone();
+        t.rename_no_common(&mut HashMap::default(), &mut -10);
+        t
+    }
+
+    /// Builds an index of random terms, every other of which is removed afterwards.
+    /// Returns the index and the terms still in it, along with their IDs.
+    fn random_index(rng: &mut Rng) -> (PDTree, Vec<(Term, u64)>) {
+        let mut pd_tree = PDTree::new();
+        let mut terms = Vec::new();
+        for id in 0..100 {
+            let t = random_term(rng, 3);
+            pd_tree.add_eq_to_index(&t, &Term::new_truth(), true, true, id);
+            terms.push((t, id));
+        }
+        for &(ref t, id) in terms.iter().filter(|&&(_, id)| id % 2 == 1) {
+            pd_tree.remove_eq_from_index(t, id);
+        }
+        terms.retain(|&(_, id)| id % 2 == 0);
+        (pd_tree, terms)
+    }
+
+    #[test]
+    fn pd_tree_remove_random() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let (mut pd_tree, terms) = random_index(&mut rng);
+        for &(ref t, id) in &terms {
+            pd_tree.remove_eq_from_index(t, id);
+        }
+        assert!(pd_tree.is_empty());
+    }
+
+    #[test]
+    fn pd_tree_retrieval_random() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..20 {
+            let (pd_tree, terms) = random_index(&mut rng);
+            for _ in 0..50 {
+                let q = random_term(&mut rng, 3);
+
+                let mut generalizations = pd_tree
+                    .iter_generalizations(&q, true)
+                    .map(|x| x.4)
+                    .collect::<Vec<_>>();
+                let mut expected = terms
+                    .iter()
+                    .filter(|(t, _)| term_match(t, &q).is_some())
+                    .map(|&(_, id)| id)
+                    .collect::<Vec<_>>();
+                generalizations.sort();
+                assert_eq!(generalizations, expected);
+
+                let mut specializations = pd_tree
+                    .iter_specializations(&q, true)
+                    .map(|x| x.4)
+                    .collect::<Vec<_>>();
+                expected = terms
+                    .iter()
+                    .filter(|(t, _)| term_match(&q, t).is_some())
+                    .map(|&(_, id)| id)
+                    .collect::<Vec<_>>();
+                specializations.sort();
+                assert_eq!(specializations, expected);
+
+                let mut unifiable = pd_tree
+                    .iter_unifiable(&q, true)
+                    .map(|x| x.4)
+                    .collect::<Vec<_>>();
+                expected = terms
+                    .iter()
+                    .filter(|(t, _)| mgu(&q, &rename_apart(t)).is_some())
+                    .map(|&(_, id)| id)
+                    .collect::<Vec<_>>();
+                unifiable.sort();
+                assert_eq!(unifiable, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn pd_tree_candidates_1() {
+        // The tree is traversed past the variable of f(x, a), so the terms ending in b are never candidates.
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let b = Term::new_constant(2);
+        let f_x_a = Term::new_function(3, vec![x.clone(), a.clone()]);
+        let f_b_b = Term::new_function(3, vec![b.clone(), b.clone()]);
+        let f_a_b = Term::new_function(3, vec![a.clone(), b.clone()]);
+        let f_b_a = Term::new_function(3, vec![b.clone(), a.clone()]);
+        let f_x_b = Term::new_function(3, vec![x.clone(), b.clone()]);
+        let g_a_a = Term::new_function(4, vec![Term::new_function(3, vec![a.clone(), a.clone()])]);
+        let f_g_a_a_a = Term::new_function(3, vec![g_a_a, a.clone()]);
+
+        let mut pd_tree = PDTree::new();
+        for (id, t) in [f_b_b, f_a_b.clone(), f_b_a, f_x_b, f_g_a_a_a]
+            .iter()
+            .enumerate()
+        {
+            pd_tree.add_eq_to_index(t, &Term::new_truth(), true, true, id as u64);
+        }
+
+        let mut specializations = pd_tree
+            .candidates(&f_x_a, false)
+            .iter()
+            .map(|x| x.4)
+            .collect::<Vec<_>>();
+        specializations.sort();
+        assert_eq!(specializations, vec![2, 4]);
+
+        let mut unifiable = pd_tree
+            .candidates(&f_x_a, true)
+            .iter()
+            .map(|x| x.4)
+            .collect::<Vec<_>>();
+        unifiable.sort();
+        assert_eq!(unifiable, vec![2, 4]);
+
+        // The variable of f(x, b) in the tree can be bound to a.
+        let mut unifiable = pd_tree
+            .candidates(&f_a_b, true)
+            .iter()
+            .map(|x| x.4)
+            .collect::<Vec<_>>();
+        unifiable.sort();
+        assert_eq!(unifiable, vec![1, 3]);
+    }
+
+    #[test]
+    fn pd_tree_unifiable_1() {
+        // Check that f(x) unifies with f(g(x, a)) when the variables are renamed apart.
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let g_x_a = Term::new_function(4, vec![x.clone(), a.clone()]);
+        let f_x = Term::new_function(3, vec![x.clone()]);
+        let f_g_x_a = Term::new_function(3, vec![g_x_a]);
+
+        let mut pd_tree = PDTree::new();
+        pd_tree.add_eq_to_index(&f_g_x_a, &x, true, true, 0);
+        let unifiable = pd_tree.iter_unifiable(&f_x, true).collect::<Vec<_>>();
+
+        assert_eq!(unifiable.len(), 1);
+        let mut l = unifiable[0].0.clone();
+        let mut q = f_x.clone();
+        l.subst(&unifiable[0].2);
+        q.subst(&unifiable[0].2);
+        assert_eq!(l, q);
+        assert_eq!(pd_tree.iter_unifiable(&f_x, false).count(), 0);
+    }
 
     #[test]
     fn prefix_order_iterator_1() {
@@ -379,7 +766,7 @@ mod test {
         let t = Term::new_truth();
 
         let mut pd_tree = PDTree::new();
-        pd_tree.add_eq_to_index(&c, &t, true, true);
+        pd_tree.add_eq_to_index(&c, &t, true, true, 0);
         let matches = pd_tree.iter_generalizations(&c, true).collect::<Vec<_>>();
 
         assert_eq!(matches.len(), 1);
@@ -397,7 +784,7 @@ mod test {
         let t = Term::new_truth();
 
         let mut pd_tree = PDTree::new();
-        pd_tree.add_eq_to_index(&f_x_x, &t, true, true);
+        pd_tree.add_eq_to_index(&f_x_x, &t, true, true, 0);
         let matches = pd_tree
             .iter_generalizations(&f_x2_x2, true)
             .collect::<Vec<_>>();
@@ -429,10 +816,10 @@ mod test {
         let g_f_a_g_b = Term::new_function(4, vec![f_a_g_b]);
 
         let mut pd_tree = PDTree::new();
-        pd_tree.add_eq_to_index(&f_a_x, &a, true, true);
-        pd_tree.add_eq_to_index(&f_b_x, &x, true, true);
-        pd_tree.add_eq_to_index(&g_f_x_x, &f_y_x, true, true);
-        pd_tree.add_eq_to_index(&g_f_x_y, &g_x, true, true);
+        pd_tree.add_eq_to_index(&f_a_x, &a, true, true, 0);
+        pd_tree.add_eq_to_index(&f_b_x, &x, true, true, 1);
+        pd_tree.add_eq_to_index(&g_f_x_x, &f_y_x, true, true, 2);
+        pd_tree.add_eq_to_index(&g_f_x_y, &g_x, true, true, 3);
 
         let matches = pd_tree
             .iter_generalizations(&g_f_a_g_b, true)
@@ -442,5 +829,27 @@ mod test {
         assert_eq!(matches[0].0, &g_f_x_y);
         assert_eq!(matches[0].1, &g_x);
         assert_eq!(matches[0].2.size(), 2);
+        assert_eq!(matches[0].4, 3);
+    }
+
+    #[test]
+    fn pd_tree_remove_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let f_a = Term::new_function(2, vec![a.clone()]);
+
+        let mut pd_tree = PDTree::new();
+        pd_tree.add_eq_to_index(&f_x, &x, true, true, 0);
+        pd_tree.add_eq_to_index(&f_a, &a, true, true, 1);
+        pd_tree.remove_eq_from_index(&f_x, 0);
+        let matches = pd_tree.iter_generalizations(&f_a, true).collect::<Vec<_>>();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].4, 1);
+
+        pd_tree.remove_eq_from_index(&f_a, 1);
+        assert_eq!(pd_tree.iter_generalizations(&f_a, true).count(), 0);
+        assert!(pd_tree.is_empty());
     }
 }
@@ -14,44 +14,54 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::cnf::renaming_info::{BOOLEAN_SORT, INDIVIDUAL_SORT};
+use crate::prover::data_structures::term_bank::{intern, TermEntry};
 use crate::prover::unification::substitution::Substitution;
 use crate::utils::hash_map::HashMap;
 use std::fmt::{Debug, Error, Formatter};
+use std::hash::{Hash, Hasher};
 use std::iter::IntoIterator;
-use std::ops::{Index, IndexMut};
-use std::slice::{Iter, IterMut};
+use std::ops::Index;
+use std::slice::Iter;
+use std::sync::Arc;
 
 /// A single term.
 /// Functions are given a positive id, variables a negative one.
 /// The id zero is for a special function symbol representing truth.
-#[derive(Eq, PartialEq, Clone, Hash)]
-pub struct Term {
-    id: i64,
-    // Terms are in two sorts, VF and P, which cannot be unified together
-    // TODO: move this info somewhere else?
-    sort_predicate: bool,
-    args: Vec<Term>,
-}
+/// Every term also has a sort, and only terms of the same sort can be unified.
+/// Distinct objects are constants which are known to be unequal to each other.
+/// Terms are hash-consed in a term bank, so they are immutable and cloning them is cheap.
+#[derive(Clone)]
+pub struct Term(Arc<TermEntry>);
 
 impl Term {
-    /// Creates a new normal function. Note that the ID passed in should be positive.
+    /// Creates a term with the given symbol and arguments, sharing it with an equal term if there is one.
+    fn new(id: i64, sort: i64, distinct_object: bool, args: Vec<Term>) -> Term {
+        Term(intern(TermEntry::new(id, sort, distinct_object, args)))
+    }
+
+    /// Creates a new normal function of the sort `$i`. Note that the ID passed in should be positive.
     pub fn new_function(id: i64, args: Vec<Term>) -> Term {
+        Term::new_sorted_function(id, INDIVIDUAL_SORT, args)
+    }
+
+    /// Creates a new normal function with the given result sort.
+    pub fn new_sorted_function(id: i64, sort: i64, args: Vec<Term>) -> Term {
         assert!(id > 0);
-        Term {
-            id: id,
-            sort_predicate: false,
-            args: args,
-        }
+        assert_ne!(sort, BOOLEAN_SORT);
+        Term::new(id, sort, false, args)
     }
 
     /// Creates a new special function. Used in the elimination of non-equality literals.
     pub fn new_special_function(id: i64, args: Vec<Term>) -> Term {
         assert!(id > 0);
-        Term {
-            id: id,
-            sort_predicate: true,
-            args: args,
-        }
+        Term::new(id, BOOLEAN_SORT, false, args)
+    }
+
+    /// Creates a new distinct object. The ID passed in should be positive.
+    pub fn new_distinct_object(id: i64) -> Term {
+        assert!(id > 0);
+        Term::new(id, INDIVIDUAL_SORT, true, Vec::new())
     }
 
     /// Creates a new constant. The ID passed in should be positive.
@@ -60,139 +70,269 @@ impl Term {
         Term::new_function(id, Vec::new())
     }
 
-    /// Creates a new variable. Note that the ID passed in should be negative.
+    /// Creates a new variable of the sort `$i`. Note that the ID passed in should be negative.
     pub fn new_variable(id: i64) -> Term {
+        Term::new_sorted_variable(id, INDIVIDUAL_SORT)
+    }
+
+    /// Creates a new variable of the given sort.
+    pub fn new_sorted_variable(id: i64, sort: i64) -> Term {
         assert!(id < 0);
-        Term {
-            id: id,
-            sort_predicate: false,
-            args: Vec::new(),
-        }
+        assert_ne!(sort, BOOLEAN_SORT);
+        Term::new(id, sort, false, Vec::new())
     }
 
     /// Create a new term representing truth. Used in the elimination of non-equality literals.
     pub fn new_truth() -> Term {
-        Term {
-            id: 0,
-            sort_predicate: true,
-            args: Vec::new(),
-        }
+        Term::new(0, BOOLEAN_SORT, false, Vec::new())
+    }
+
+    /// Creates a term with the same symbol as this one but with the given arguments.
+    pub fn with_args(&self, args: Vec<Term>) -> Term {
+        assert!(self.is_function());
+        assert_eq!(self.get_arity(), args.len());
+        Term::new(
+            self.get_id(),
+            self.get_sort(),
+            self.is_distinct_object(),
+            args,
+        )
+    }
+
+    /// Get the entry of the term in the term bank.
+    pub fn get_entry(&self) -> &TermEntry {
+        &self.0
+    }
+
+    /// Checks if the two terms are stored in the same place.
+    /// Equal terms created in the same thread always are.
+    pub fn ptr_eq(&self, other: &Term) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
     }
 
     /// Get the id of the term.
     pub fn get_id(&self) -> i64 {
-        self.id
+        self.0.get_id()
+    }
+
+    /// Get the sort of the term.
+    pub fn get_sort(&self) -> i64 {
+        self.0.get_sort()
     }
 
     /// Get the arity of the term.
     pub fn get_arity(&self) -> usize {
-        self.args.len()
+        self.0.get_args().len()
+    }
+
+    /// Get the hash of the term. It is cached, so this takes constant time.
+    pub fn get_hash(&self) -> u64 {
+        self.0.get_hash()
+    }
+
+    /// Get the depth of the term. Variables and constants have depth 1.
+    pub fn get_depth(&self) -> usize {
+        self.0.get_depth()
+    }
+
+    /// Get the variables occurring in the term and how many times they occur, sorted by their IDs.
+    pub fn get_variables(&self) -> &[(i64, u64)] {
+        self.0.get_variables()
     }
 
     /// Checks if this term is a function.
     pub fn is_function(&self) -> bool {
-        self.id >= 0
+        self.get_id() >= 0
     }
 
     /// Checks if this term represents truth.
     #[allow(dead_code)]
     pub fn is_truth(&self) -> bool {
-        self.id == 0
+        self.get_id() == 0
     }
 
     /// Check if the term is a special function.
     pub fn is_special_function(&self) -> bool {
-        self.sort_predicate
+        self.get_sort() == BOOLEAN_SORT
+    }
+
+    /// Checks if this term is a distinct object.
+    pub fn is_distinct_object(&self) -> bool {
+        self.0.is_distinct_object()
     }
 
     /// Checks if this term is a variable.
     pub fn is_variable(&self) -> bool {
-        self.id < 0
+        self.get_id() < 0
+    }
+
+    /// Checks if this term contains no variables.
+    pub fn is_ground(&self) -> bool {
+        self.get_variables().is_empty()
+    }
+
+    /// Checks if the variable with the given ID occurs in the term.
+    pub fn has_variable(&self, id: i64) -> bool {
+        self.get_variables()
+            .binary_search_by_key(&id, |&(x, _)| x)
+            .is_ok()
+    }
+
+    /// Get the smallest variable ID in the term, or zero if there are no variables.
+    pub fn min_variable_id(&self) -> i64 {
+        self.get_variables().first().map_or(0, |&(x, _)| x)
     }
 
     /// Checks if a given term occurs in the term.
     pub fn occurs(&self, term: &Term) -> bool {
-        self == term || self.args.iter().any(|t| t.occurs(term))
+        if term.is_variable() {
+            self.has_variable(term.get_id())
+        } else {
+            self.get_depth() >= term.get_depth()
+                && (self == term || self.iter().any(|t| t.occurs(term)))
+        }
     }
 
     /// Checks if a given variable or function occurs in the term as a subterm.
     pub fn occurs_proper(&self, term: &Term) -> bool {
-        self.args.iter().any(|t| t.occurs(term))
+        self.iter().any(|t| t.occurs(term))
+    }
+
+    /// Get the amount of occurrences of the unary function with the given ID.
+    /// Usually this takes constant time, but if the term contains several unary functions they have to be counted.
+    pub fn unary_function_count(&self, id: i64) -> u64 {
+        match self.0.unary_function_count(id) {
+            Some(n) => n,
+            None => {
+                let n = self.iter().map(|t| t.unary_function_count(id)).sum();
+                if self.get_id() == id && self.get_arity() == 1 {
+                    n + 1
+                } else {
+                    n
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the term with the arguments changed by the given function.
+    /// The function returns None if the argument does not change.
+    /// Returns None if no argument changed, so that unchanged terms are not rebuilt.
+    fn map_args<F: FnMut(&Term) -> Option<Term>>(&self, mut f: F) -> Option<Term> {
+        let mut new_args: Option<Vec<Term>> = None;
+        for (i, arg) in self.iter().enumerate() {
+            if let Some(new_arg) = f(arg) {
+                new_args.get_or_insert_with(|| self.0.get_args().to_vec())[i] = new_arg;
+            }
+        }
+        new_args.map(|args| self.with_args(args))
     }
 
     /// Substitute all instances of the term s with a term t.
     pub fn subst_single(&mut self, s: &Term, t: &Term) {
+        if let Some(new_t) = self.subst_single_helper(s, t) {
+            *self = new_t;
+        }
+    }
+
+    /// Helper for above. Returns None if the term does not change.
+    fn subst_single_helper(&self, s: &Term, t: &Term) -> Option<Term> {
         if self == s {
-            *self = t.clone();
+            Some(t.clone())
+        } else if self.get_depth() <= s.get_depth()
+            || (s.is_variable() && !self.has_variable(s.get_id()))
+        {
+            None
         } else {
-            for arg in &mut self.args {
-                arg.subst_single(s, t);
-            }
+            self.map_args(|arg| arg.subst_single_helper(s, t))
         }
     }
 
     /// Substitutes according to the mapping.
+    /// Ground subterms are left as they are without looking into them.
     pub fn subst(&mut self, substitution: &Substitution) {
-        if let Some(t) = substitution.get(self) {
-            *self = t.clone();
+        if let Some(new_t) = self.subst_helper(substitution) {
+            *self = new_t;
+        }
+    }
+
+    /// Helper for above. Returns None if the term does not change.
+    fn subst_helper(&self, substitution: &Substitution) -> Option<Term> {
+        if self.is_ground() {
+            None
+        } else if let Some(t) = substitution.get(self) {
+            Some(t.clone())
         } else {
-            for x in &mut self.args {
-                x.subst(substitution);
-            }
+            self.map_args(|arg| arg.subst_helper(substitution))
         }
     }
 
     /// Calculates the symbol count with given weights to function and variable symbols.
+    /// The counts are cached, so this takes constant time.
     pub fn symbol_count(&self, f_value: u64, v_value: u64) -> u64 {
-        if self.is_variable() {
-            v_value
-        } else {
-            self.iter().fold(f_value, |acc, sub_t| {
-                acc + sub_t.symbol_count(f_value, v_value)
-            })
-        }
+        self.0.symbol_count(f_value, v_value)
     }
 
     /// Rename all variables in a term so that it has no variables in common with other clauses.
     pub fn rename_no_common(&mut self, sfn: &mut HashMap<i64, i64>, var_cnt: &mut i64) {
+        if let Some(new_t) = self.rename_no_common_helper(sfn, var_cnt) {
+            *self = new_t;
+        }
+    }
+
+    /// Helper for above. Returns None if the term does not change.
+    fn rename_no_common_helper(
+        &self,
+        sfn: &mut HashMap<i64, i64>,
+        var_cnt: &mut i64,
+    ) -> Option<Term> {
         if self.is_variable() {
-            if let Some(&t) = sfn.get(&self.id) {
+            let id = if let Some(&t) = sfn.get(&self.get_id()) {
                 assert!(t < 0);
-                self.id = t;
+                t
             } else {
                 *var_cnt -= 1;
-                sfn.insert(self.id, *var_cnt);
-                self.id = *var_cnt;
+                sfn.insert(self.get_id(), *var_cnt);
+                *var_cnt
+            };
+            if id == self.get_id() {
+                None
+            } else {
+                Some(Term::new_sorted_variable(id, self.get_sort()))
             }
+        } else if self.is_ground() {
+            None
         } else {
-            for arg in &mut self.args {
-                arg.rename_no_common(sfn, var_cnt);
-            }
+            self.map_args(|arg| arg.rename_no_common_helper(sfn, var_cnt))
         }
     }
 
     /// Used for iterating the subterms of a term.
-    pub fn iter(&self) -> Iter<Term> {
-        self.args.iter()
+    pub fn iter(&self) -> Iter<'_, Term> {
+        self.0.get_args().iter()
     }
+}
 
-    /// Used for iterating the literals of the clause with the option of mutating them.
-    pub fn iter_mut(&mut self) -> IterMut<Term> {
-        self.args.iter_mut()
+impl PartialEq for Term {
+    /// Terms created in the same thread are equal iff they are stored in the same place.
+    /// Otherwise the cached hashes are compared before the terms themselves.
+    fn eq(&self, other: &Term) -> bool {
+        self.ptr_eq(other) || *self.0 == *other.0
     }
 }
 
-impl Index<usize> for Term {
-    type Output = Term;
+impl Eq for Term {}
 
-    fn index(&self, index: usize) -> &Term {
-        &self.args[index]
+impl Hash for Term {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.get_hash());
     }
 }
 
-impl IndexMut<usize> for Term {
-    fn index_mut(&mut self, index: usize) -> &mut Term {
-        &mut self.args[index]
+impl Index<usize> for Term {
+    type Output = Term;
+
+    fn index(&self, index: usize) -> &Term {
+        &self.0.get_args()[index]
     }
 }
 
@@ -202,7 +342,7 @@ impl IntoIterator for Term {
 
     /// Consumes the term and returns an iterator over the subterms.
     fn into_iter(self) -> Self::IntoIter {
-        self.args.into_iter()
+        self.0.get_args().to_vec().into_iter()
     }
 }
 
@@ -0,0 +1,360 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::HashMap;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::sync::{Arc, Weak};
+
+/// The bank is purged at the earliest when it has this many entries.
+const MIN_PURGE_LIMIT: usize = 4096;
+
+/// Which unary functions occur in a term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryFunctions {
+    Zero,
+    One(i64, u64),
+    Many,
+}
+
+/// A term stored in the bank, along with some cached information about it.
+pub struct TermEntry {
+    id: i64,
+    sort: i64,
+    distinct_object: bool,
+    args: Vec<Term>,
+    hash: u64,
+    function_count: u64,
+    variable_count: u64,
+    depth: usize,
+    variables: Vec<(i64, u64)>,
+    unary_functions: UnaryFunctions,
+}
+
+impl TermEntry {
+    /// Creates a new entry. The cached information is calculated from the cached information of the arguments.
+    pub fn new(id: i64, sort: i64, distinct_object: bool, args: Vec<Term>) -> TermEntry {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_i64(id);
+        hasher.write_i64(sort);
+        hasher.write_u8(distinct_object as u8);
+
+        let mut entry = TermEntry {
+            id,
+            sort,
+            distinct_object,
+            args: Vec::new(),
+            hash: 0,
+            function_count: 0,
+            variable_count: 0,
+            depth: 1,
+            variables: Vec::new(),
+            unary_functions: UnaryFunctions::Zero,
+        };
+
+        if id < 0 {
+            entry.variable_count = 1;
+            entry.variables.push((id, 1));
+        } else {
+            entry.function_count = 1;
+            if args.len() == 1 {
+                entry.unary_functions = UnaryFunctions::One(id, 1);
+            }
+            for arg in &args {
+                hasher.write_u64(arg.get_hash());
+                entry.function_count += arg.symbol_count(1, 0);
+                entry.variable_count += arg.symbol_count(0, 1);
+                entry.depth = entry.depth.max(arg.get_depth() + 1);
+                entry.variables.extend_from_slice(arg.get_variables());
+                entry.unary_functions = add_unary_functions(entry.unary_functions, arg);
+            }
+            entry.variables = merge_variables(entry.variables);
+        }
+
+        entry.hash = hasher.finish();
+        entry.args = args;
+        entry
+    }
+
+    /// Get the ID of the term.
+    pub fn get_id(&self) -> i64 {
+        self.id
+    }
+
+    /// Get the sort of the term.
+    pub fn get_sort(&self) -> i64 {
+        self.sort
+    }
+
+    /// Checks if the term is a distinct object.
+    pub fn is_distinct_object(&self) -> bool {
+        self.distinct_object
+    }
+
+    /// Get the arguments of the term.
+    pub fn get_args(&self) -> &[Term] {
+        &self.args
+    }
+
+    /// Get the hash of the term. Equal terms have equal hashes, no matter which bank they are stored in.
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Calculates the symbol count with given weights to function and variable symbols.
+    pub fn symbol_count(&self, f_value: u64, v_value: u64) -> u64 {
+        self.function_count * f_value + self.variable_count * v_value
+    }
+
+    /// Get the depth of the term. Variables and constants have depth 1.
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the variables occurring in the term and how many times they occur, sorted by their IDs.
+    pub fn get_variables(&self) -> &[(i64, u64)] {
+        &self.variables
+    }
+
+    /// Get the amount of occurrences of the unary function with the given ID.
+    /// Returns None if the term contains more than one unary function, in which case the count is not cached.
+    pub fn unary_function_count(&self, id: i64) -> Option<u64> {
+        match self.unary_functions {
+            UnaryFunctions::Zero => Some(0),
+            UnaryFunctions::One(f, n) => Some(if f == id { n } else { 0 }),
+            UnaryFunctions::Many => None,
+        }
+    }
+}
+
+impl PartialEq for TermEntry {
+    fn eq(&self, other: &TermEntry) -> bool {
+        self.hash == other.hash
+            && self.id == other.id
+            && self.sort == other.sort
+            && self.distinct_object == other.distinct_object
+            && self.args == other.args
+    }
+}
+
+impl Eq for TermEntry {}
+
+/// Adds the unary functions of a term to the given ones.
+fn add_unary_functions(current: UnaryFunctions, t: &Term) -> UnaryFunctions {
+    match (current, t.get_entry().unary_functions) {
+        (UnaryFunctions::Zero, other) | (other, UnaryFunctions::Zero) => other,
+        (UnaryFunctions::One(f, n), UnaryFunctions::One(g, m)) if f == g => {
+            UnaryFunctions::One(f, n + m)
+        }
+        _ => UnaryFunctions::Many,
+    }
+}
+
+/// Sorts the variables and sums up the occurrences of the same variable.
+fn merge_variables(mut variables: Vec<(i64, u64)>) -> Vec<(i64, u64)> {
+    variables.sort_unstable();
+    let mut merged: Vec<(i64, u64)> = Vec::with_capacity(variables.len());
+    for (x, n) in variables {
+        match merged.last_mut() {
+            Some(&mut (y, ref mut m)) if x == y => *m += n,
+            _ => merged.push((x, n)),
+        }
+    }
+    merged
+}
+
+/// Stores terms so that every distinct term in use is stored only once.
+/// Subterms are shared, so copying a term is just copying a pointer.
+/// The bank only holds weak references, so terms which are no longer used are freed as usual.
+/// Their entries are removed from the bank once in a while.
+pub struct TermBank {
+    table: HashMap<u64, Weak<TermEntry>>,
+    purge_limit: usize,
+}
+
+impl Default for TermBank {
+    fn default() -> TermBank {
+        TermBank::new()
+    }
+}
+
+impl TermBank {
+    /// Creates an empty term bank.
+    pub fn new() -> TermBank {
+        TermBank {
+            table: HashMap::default(),
+            purge_limit: MIN_PURGE_LIMIT,
+        }
+    }
+
+    /// Get the amount of entries in the bank.
+    /// Terms which are no longer used are counted until the next purge.
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Checks if the bank is empty.
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Returns the stored version of the term if there is one, otherwise stores the term.
+    pub fn intern(&mut self, entry: TermEntry) -> Arc<TermEntry> {
+        let hash = entry.get_hash();
+        if let Some(stored) = self.table.get(&hash).and_then(Weak::upgrade) {
+            // On a hash collision the new term is just left unshared.
+            return if *stored == entry {
+                stored
+            } else {
+                Arc::new(entry)
+            };
+        }
+
+        if self.table.len() >= self.purge_limit {
+            self.purge();
+        }
+        let stored = Arc::new(entry);
+        self.table.insert(hash, Arc::downgrade(&stored));
+        stored
+    }
+
+    /// Removes the entries of the terms which are no longer used.
+    pub fn purge(&mut self) {
+        self.table.retain(|_, entry| entry.strong_count() > 0);
+        self.purge_limit = MIN_PURGE_LIMIT.max(2 * self.table.len());
+    }
+}
+
+thread_local! {
+    static TERM_BANK: RefCell<TermBank> = RefCell::new(TermBank::new());
+}
+
+/// Stores the term in the bank of the current thread.
+/// Terms can be freely sent between threads, but are only shared with terms created in the same thread.
+pub fn intern(entry: TermEntry) -> Arc<TermEntry> {
+    let mut entry = Some(entry);
+    TERM_BANK
+        .try_with(|bank| {
+            let entry = entry.take().expect("The entry is only taken once");
+            bank.borrow_mut().intern(entry)
+        })
+        .unwrap_or_else(|_| Arc::new(entry.take().expect("The bank was not accessible")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TermBank, TermEntry};
+    use crate::cnf::renaming_info::INDIVIDUAL_SORT;
+    use crate::prover::data_structures::term::Term;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn intern_1() {
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x_a = Term::new_function(2, vec![x.clone(), a.clone()]);
+        let g_f_x_a = Term::new_function(3, vec![f_x_a.clone(), f_x_a.clone()]);
+
+        let mut bank = TermBank::new();
+        let e1 = bank.intern(TermEntry::new(
+            3,
+            INDIVIDUAL_SORT,
+            false,
+            vec![f_x_a.clone(), f_x_a.clone()],
+        ));
+        let e2 = bank.intern(TermEntry::new(
+            3,
+            INDIVIDUAL_SORT,
+            false,
+            vec![f_x_a.clone(), f_x_a.clone()],
+        ));
+        let e3 = bank.intern(TermEntry::new(2, INDIVIDUAL_SORT, false, vec![x, a]));
+
+        assert_eq!(bank.len(), 2);
+        assert!(Arc::ptr_eq(&e1, &e2));
+        assert!(!Arc::ptr_eq(&e1, &e3));
+        assert_eq!(
+            e1.get_args(),
+            g_f_x_a.iter().cloned().collect::<Vec<_>>().as_slice()
+        );
+    }
+
+    #[test]
+    fn intern_shares_subterms() {
+        let x = Term::new_variable(-1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let g_f_x_1 = Term::new_function(3, vec![Term::new_function(2, vec![x.clone()])]);
+        let g_f_x_2 = Term::new_function(3, vec![f_x.clone()]);
+
+        assert!(g_f_x_1.ptr_eq(&g_f_x_2));
+        assert!(g_f_x_1[0].ptr_eq(&f_x));
+    }
+
+    #[test]
+    fn purge_1() {
+        let mut bank = TermBank::new();
+        let a = bank.intern(TermEntry::new(1, INDIVIDUAL_SORT, false, Vec::new()));
+        {
+            let _b = bank.intern(TermEntry::new(2, INDIVIDUAL_SORT, false, Vec::new()));
+        }
+        assert_eq!(bank.len(), 2);
+        bank.purge();
+        assert_eq!(bank.len(), 1);
+
+        let a2 = bank.intern(TermEntry::new(1, INDIVIDUAL_SORT, false, Vec::new()));
+        assert!(Arc::ptr_eq(&a, &a2));
+    }
+
+    #[test]
+    fn metadata_1() {
+        let x = Term::new_variable(-1);
+        let y = Term::new_variable(-2);
+        let a = Term::new_constant(1);
+        let f_y_x = Term::new_function(2, vec![y.clone(), x.clone()]);
+        let h_x = Term::new_function(4, vec![x.clone()]);
+        let g_f_y_x_h_x = Term::new_function(3, vec![f_y_x.clone(), h_x.clone()]);
+        let h_h_a = Term::new_function(4, vec![Term::new_function(4, vec![a.clone()])]);
+        let k_h_x = Term::new_function(5, vec![h_x.clone()]);
+
+        assert!(!g_f_y_x_h_x.is_ground());
+        assert!(h_h_a.is_ground());
+        assert_eq!(g_f_y_x_h_x.get_depth(), 3);
+        assert_eq!(g_f_y_x_h_x.get_variables(), &[(-2, 1), (-1, 2)]);
+        assert_eq!(g_f_y_x_h_x.min_variable_id(), -2);
+        assert_eq!(h_h_a.min_variable_id(), 0);
+        assert_eq!(g_f_y_x_h_x.symbol_count(2, 1), 9);
+        assert_eq!(h_h_a.unary_function_count(4), 2);
+        assert_eq!(g_f_y_x_h_x.unary_function_count(4), 1);
+        assert_eq!(g_f_y_x_h_x.unary_function_count(2), 0);
+        assert_eq!(k_h_x.unary_function_count(4), 1);
+        assert_eq!(k_h_x.unary_function_count(5), 1);
+    }
+
+    #[test]
+    fn equal_across_threads() {
+        let f_a = Term::new_function(2, vec![Term::new_constant(1)]);
+        let f_a_2 = thread::spawn(|| Term::new_function(2, vec![Term::new_constant(1)]))
+            .join()
+            .unwrap();
+
+        assert!(!f_a.ptr_eq(&f_a_2));
+        assert_eq!(f_a, f_a_2);
+        assert_ne!(f_a, Term::new_function(2, vec![Term::new_constant(3)]));
+    }
+}
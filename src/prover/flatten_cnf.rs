@@ -16,6 +16,7 @@
 
 use crate::cnf::ast::Formula;
 use crate::cnf::ast::Term as CnfTerm;
+use crate::cnf::renaming_info::RenamingInfo;
 use crate::prover::data_structures::clause::Clause;
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::data_structures::term::Term as ProverTerm;
@@ -23,73 +24,83 @@ use crate::prover::data_structures::term::Term as ProverTerm;
 /// Turns a formula in CNF into a flat representation more suited for the prover.
 /// Also converts the formula into pure equational logic.
 /// The trivial case of the formula reducing to just True or False should be handled separately.
-pub fn flatten_cnf(f: Formula) -> Vec<Clause> {
-    transform(f)
+/// The sorts of the terms are taken from the renaming info.
+pub fn flatten_cnf(f: Formula, ri: &RenamingInfo) -> Vec<Clause> {
+    transform(f, ri)
 }
 
-fn transform(f: Formula) -> Vec<Clause> {
+fn transform(f: Formula, ri: &RenamingInfo) -> Vec<Clause> {
     match f {
-        Formula::Predicate(s, args) => vec![Clause::new(vec![create_literal(false, s, args)])],
+        Formula::Predicate(s, args) => {
+            vec![Clause::new(vec![create_literal(false, s, args, ri)])]
+        }
         Formula::Not(p) => {
             if let Formula::Predicate(ref s, ref args) = *p {
                 vec![Clause::new(vec![create_literal(
                     true,
-                    s.clone(),
+                    *s,
                     args.clone(),
+                    ri,
                 )])]
             } else {
                 panic!("The CNF transformation failed due to some kind of a bug")
             }
         }
-        Formula::Or(l) => {
-            vec![Clause::new(l.into_iter().flat_map(transform_or).collect())]
-        }
-        Formula::And(l) => l.into_iter().flat_map(transform).collect(),
+        Formula::Or(l) => vec![Clause::new(
+            l.into_iter().flat_map(|x| transform_or(x, ri)).collect(),
+        )],
+        Formula::And(l) => l.into_iter().flat_map(|x| transform(x, ri)).collect(),
         _ => panic!("The CNF transformation failed due to some kind of a bug"),
     }
 }
 
-fn transform_or(f: Formula) -> Vec<Literal> {
+fn transform_or(f: Formula, ri: &RenamingInfo) -> Vec<Literal> {
     match f {
-        Formula::Predicate(s, args) => vec![create_literal(false, s, args)],
+        Formula::Predicate(s, args) => vec![create_literal(false, s, args, ri)],
         Formula::Not(p) => {
             if let Formula::Predicate(ref s, ref args) = *p {
-                vec![create_literal(true, s.clone(), args.clone())]
+                vec![create_literal(true, *s, args.clone(), ri)]
             } else {
                 panic!("The CNF transformation failed due to some kind of a bug")
             }
         }
-        Formula::Or(l) => l.into_iter().flat_map(transform_or).collect(),
+        Formula::Or(l) => l.into_iter().flat_map(|x| transform_or(x, ri)).collect(),
         _ => panic!("The CNF transformation failed due to some kind of a bug"),
     }
 }
 
-fn create_literal(negated: bool, id: i64, args: Vec<CnfTerm>) -> Literal {
+fn create_literal(negated: bool, id: i64, args: Vec<CnfTerm>, ri: &RenamingInfo) -> Literal {
     if id == 0 {
         assert_eq!(args.len(), 2);
         Literal::new(
             negated,
-            create_term(args[0].clone(), false),
-            create_term(args[1].clone(), false),
+            create_term(args[0].clone(), false, ri),
+            create_term(args[1].clone(), false, ri),
         )
     } else {
         Literal::new(
             negated,
-            create_term(CnfTerm::Function(id, args), true),
+            create_term(CnfTerm::Function(id, args), true, ri),
             ProverTerm::new_truth(),
         )
     }
 }
 
-fn create_term(t: CnfTerm, special_fn: bool) -> ProverTerm {
+fn create_term(t: CnfTerm, special_fn: bool, ri: &RenamingInfo) -> ProverTerm {
     match t {
-        CnfTerm::Variable(id) => ProverTerm::new_variable(id),
+        CnfTerm::Variable(id) => ProverTerm::new_sorted_variable(id, ri.get_variable_sort(id)),
+        CnfTerm::Function(id, ref args) if args.is_empty() && ri.is_distinct_object(id) => {
+            ProverTerm::new_distinct_object(id)
+        }
         CnfTerm::Function(id, args) => {
-            let new_args = args.into_iter().map(|t2| create_term(t2, false)).collect();
+            let new_args = args
+                .into_iter()
+                .map(|t2| create_term(t2, false, ri))
+                .collect();
             if special_fn {
                 ProverTerm::new_special_function(id, new_args)
             } else {
-                ProverTerm::new_function(id, new_args)
+                ProverTerm::new_sorted_function(id, ri.get_function_sort(id), new_args)
             }
         }
     }
@@ -17,20 +17,27 @@
 // TODO: clean this crap up
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::{Derivation, InferenceRule};
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::data_structures::term::Term;
+use crate::prover::inference::literal_selection::LiteralSelection;
 use crate::prover::inference::maximality::literal_maximal_in;
 use crate::prover::ordering::term_ordering::TermOrdering;
 use crate::prover::unification::full_unification::mgu;
 
 /// Infers new clauses by equality factoring
+/// Clauses with selected literals are not factored.
 /// Returns the amount of inferred clauses.
 pub fn equality_factoring(
     term_ordering: &TermOrdering,
+    literal_selection: LiteralSelection,
     cl: &Clause,
     generated: &mut Vec<Clause>,
 ) -> usize {
     let mut ef_count = 0;
+    if !literal_selection.select(cl).is_empty() {
+        return ef_count;
+    }
 
     for (i, l1) in cl.iter().enumerate() {
         if l1.is_negative() {
@@ -89,7 +96,7 @@ pub fn equality_factoring(
     ef_count
 }
 
-#[cfg_attr(feature = "clippy", allow(too_many_arguments))]
+#[allow(clippy::too_many_arguments)]
 fn equality_factoring_create_new(
     term_ordering: &TermOrdering,
     cl: &Clause,
@@ -115,6 +122,10 @@ fn equality_factoring_create_new(
                 let mut new_ineq_lit = Literal::new(true, t.clone(), v.clone());
                 new_ineq_lit.subst(&sigma);
                 new_cl.add_literal(new_ineq_lit);
+                new_cl.set_derivation(Derivation::new_inference(
+                    InferenceRule::EqualityFactoring,
+                    &[cl.get_id()],
+                ));
                 generated.push(new_cl);
                 ef_count += 1;
             }
@@ -125,4 +136,49 @@ fn equality_factoring_create_new(
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::equality_factoring;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::prover::inference::literal_selection::LiteralSelection;
+    use crate::prover::ordering::precedence::Precedence;
+    use crate::prover::ordering::term_ordering::TermOrdering;
+    use crate::prover::ordering::weight::Weight;
+
+    #[test]
+    fn equality_factoring_selected() {
+        // f(x) = a | f(b) = a | c <> d can only be factored if c <> d is not selected.
+        let term_ordering = TermOrdering::KBO(Precedence::default(), Weight::SimpleWeight, None);
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let b = Term::new_constant(2);
+        let c = Term::new_constant(3);
+        let d = Term::new_constant(4);
+        let f_x = Term::new_function(5, vec![x]);
+        let f_b = Term::new_function(5, vec![b]);
+        let mut cl = Clause::new(vec![
+            Literal::new(false, f_x, a.clone()),
+            Literal::new(false, f_b, a),
+            Literal::new(true, c, d),
+        ]);
+        cl.set_id(0);
+
+        let mut generated = Vec::new();
+        let count = equality_factoring(&term_ordering, LiteralSelection::None, &cl, &mut generated);
+        assert!(count > 0);
+        assert_eq!(count, generated.len());
+
+        let mut generated = Vec::new();
+        assert_eq!(
+            equality_factoring(
+                &term_ordering,
+                LiteralSelection::AllNegative,
+                &cl,
+                &mut generated
+            ),
+            0
+        );
+        assert!(generated.is_empty());
+    }
+}
@@ -15,21 +15,26 @@
 //
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::{Derivation, InferenceRule};
+use crate::prover::inference::literal_selection::LiteralSelection;
 use crate::prover::inference::maximality::literal_maximal_in;
 use crate::prover::ordering::term_ordering::TermOrdering;
 use crate::prover::unification::full_unification::mgu;
 
 /// Infers new clauses by (ordered) equality resolution.
+/// If the clause has selected literals, only those are resolved.
 /// Returns the amount of inferred clauses.
 pub fn equality_resolution(
     term_ordering: &TermOrdering,
+    literal_selection: LiteralSelection,
     cl: &Clause,
     generated: &mut Vec<Clause>,
 ) -> usize {
     let mut er_count = 0;
+    let selected = literal_selection.select(cl);
 
     for (i, l) in cl.iter().enumerate() {
-        if l.is_negative() {
+        if l.is_negative() && (selected.is_empty() || selected.contains(&i)) {
             if let Some(sigma) = mgu(l.get_lhs(), l.get_rhs()) {
                 let mut new_cl = cl.clone();
                 new_cl.subst(&sigma);
@@ -38,7 +43,11 @@ pub fn equality_resolution(
                 assert_eq!(new_l.get_lhs(), new_l.get_rhs());
                 assert_eq!(new_cl.size() + 1, cl.size());
 
-                if literal_maximal_in(term_ordering, &new_cl, &new_l) {
+                if !selected.is_empty() || literal_maximal_in(term_ordering, &new_cl, &new_l) {
+                    new_cl.set_derivation(Derivation::new_inference(
+                        InferenceRule::EqualityResolution,
+                        &[cl.get_id()],
+                    ));
                     generated.push(new_cl);
                     er_count += 1;
                 }
@@ -50,4 +59,54 @@ pub fn equality_resolution(
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::equality_resolution;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::prover::inference::literal_selection::LiteralSelection;
+    use crate::prover::ordering::precedence::Precedence;
+    use crate::prover::ordering::term_ordering::TermOrdering;
+    use crate::prover::ordering::weight::Weight;
+
+    #[test]
+    fn equality_resolution_selected() {
+        // g(x, x, x) <> h(y, y, y) | f(z, a) <> f(z, w), where only the second literal can be resolved.
+        let term_ordering = TermOrdering::KBO(Precedence::default(), Weight::SimpleWeight, None);
+        let x = Term::new_variable(-1);
+        let y = Term::new_variable(-2);
+        let z = Term::new_variable(-3);
+        let w = Term::new_variable(-4);
+        let a = Term::new_constant(1);
+        let f_z_a = Term::new_function(2, vec![z.clone(), a]);
+        let f_z_w = Term::new_function(2, vec![z, w]);
+        let g_x_x_x = Term::new_function(3, vec![x.clone(), x.clone(), x]);
+        let h_y_y_y = Term::new_function(4, vec![y.clone(), y.clone(), y]);
+        let mut cl = Clause::new(vec![
+            Literal::new(true, g_x_x_x, h_y_y_y),
+            Literal::new(true, f_z_a, f_z_w),
+        ]);
+        cl.set_id(0);
+
+        let mut generated = Vec::new();
+        assert_eq!(
+            equality_resolution(&term_ordering, LiteralSelection::None, &cl, &mut generated),
+            1
+        );
+        assert_eq!(generated[0].size(), 1);
+
+        // The selected literal is the first one, which can't be resolved.
+        let mut generated = Vec::new();
+        assert_eq!(LiteralSelection::MaxWeightNegative.select(&cl), vec![0]);
+        assert_eq!(
+            equality_resolution(
+                &term_ordering,
+                LiteralSelection::MaxWeightNegative,
+                &cl,
+                &mut generated
+            ),
+            0
+        );
+        assert!(generated.is_empty());
+    }
+}
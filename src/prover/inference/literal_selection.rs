@@ -0,0 +1,139 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::literal::Literal;
+use std::str::FromStr;
+
+/// Decides which negative literals of a clause are selected.
+/// If a clause has selected literals, only those can take part in inferences,
+/// instead of the maximal literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralSelection {
+    /// Never select anything, so that inferences are only restricted by maximality.
+    None,
+    /// Select the negative literal with the largest symbol count.
+    MaxWeightNegative,
+    /// Select all negative literals.
+    AllNegative,
+    /// Select the smallest ground negative literal if there is one,
+    /// otherwise the negative literal with the largest difference between the sizes of its sides.
+    Complex,
+}
+
+/// Get the difference between the sizes of the sides of a literal.
+fn size_difference(l: &Literal) -> u64 {
+    let lhs_size = l.get_lhs().symbol_count(1, 1);
+    let rhs_size = l.get_rhs().symbol_count(1, 1);
+    lhs_size.max(rhs_size) - lhs_size.min(rhs_size)
+}
+
+impl LiteralSelection {
+    /// Get the indices of the selected literals of a clause in ascending order.
+    pub fn select(&self, cl: &Clause) -> Vec<usize> {
+        let negative = cl.iter().enumerate().filter(|&(_, l)| l.is_negative());
+        // When there are several equally good literals, the first one is selected.
+        let best = match *self {
+            LiteralSelection::None => None,
+            LiteralSelection::AllNegative => return negative.map(|(i, _)| i).collect(),
+            LiteralSelection::MaxWeightNegative => negative
+                .rev()
+                .max_by_key(|&(_, l)| l.symbol_count(1, 1))
+                .map(|(i, _)| i),
+            LiteralSelection::Complex => {
+                let negative = negative.collect::<Vec<_>>();
+                let ground = negative
+                    .iter()
+                    .filter(|&&(_, l)| l.get_lhs().is_ground() && l.get_rhs().is_ground())
+                    .min_by_key(|&&(_, l)| l.symbol_count(1, 1));
+                ground
+                    .or_else(|| {
+                        negative
+                            .iter()
+                            .rev()
+                            .max_by_key(|&&(_, l)| size_difference(l))
+                    })
+                    .map(|&(i, _)| i)
+            }
+        };
+        best.into_iter().collect()
+    }
+}
+
+impl FromStr for LiteralSelection {
+    type Err = String;
+
+    /// Parses a literal selection written as `none`, `max-weight`, `all-negative` or `complex`.
+    fn from_str(s: &str) -> Result<LiteralSelection, String> {
+        match s.trim() {
+            "none" => Ok(LiteralSelection::None),
+            "max-weight" => Ok(LiteralSelection::MaxWeightNegative),
+            "all-negative" => Ok(LiteralSelection::AllNegative),
+            "complex" => Ok(LiteralSelection::Complex),
+            _ => Err(format!("Unknown literal selection {}", s.trim())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LiteralSelection;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+
+    #[test]
+    fn select_1() {
+        // f(x) = c | g(f(x)) != c | f(c) != c | x != c
+        let x = Term::new_variable(-1);
+        let c = Term::new_constant(1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let f_c = Term::new_function(2, vec![c.clone()]);
+        let g_f_x = Term::new_function(3, vec![f_x.clone()]);
+        let cl = Clause::new(vec![
+            Literal::new(false, f_x, c.clone()),
+            Literal::new(true, g_f_x, c.clone()),
+            Literal::new(true, f_c, c.clone()),
+            Literal::new(true, x, c),
+        ]);
+
+        assert!(LiteralSelection::None.select(&cl).is_empty());
+        assert_eq!(LiteralSelection::AllNegative.select(&cl), vec![1, 2, 3]);
+        assert_eq!(LiteralSelection::MaxWeightNegative.select(&cl), vec![1]);
+        assert_eq!(LiteralSelection::Complex.select(&cl), vec![2]);
+    }
+
+    #[test]
+    fn select_2() {
+        // Without ground negative literals the one with the largest size difference is selected.
+        let x = Term::new_variable(-1);
+        let y = Term::new_variable(-2);
+        let f_x = Term::new_function(1, vec![x.clone()]);
+        let f_y = Term::new_function(1, vec![y.clone()]);
+        let g_f_x = Term::new_function(2, vec![f_x.clone()]);
+        let cl = Clause::new(vec![
+            Literal::new(true, f_x.clone(), f_y),
+            Literal::new(true, g_f_x, y),
+            Literal::new(false, f_x, x),
+        ]);
+
+        assert_eq!(LiteralSelection::Complex.select(&cl), vec![1]);
+        assert_eq!(LiteralSelection::MaxWeightNegative.select(&cl), vec![0]);
+        assert!(LiteralSelection::None
+            .select(&Clause::new(Vec::new()))
+            .is_empty());
+    }
+}
@@ -25,3 +25,6 @@ pub mod superposition;
 
 /// Stuff for checking maximality of literals in clauses.
 mod maximality;
+
+/// Contains the functions for selecting negative literals of clauses for inferences.
+pub mod literal_selection;
@@ -17,29 +17,29 @@
 // TODO: clean this crap up.
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::{Derivation, InferenceRule};
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::data_structures::term::Term;
+use crate::prover::inference::literal_selection::LiteralSelection;
 use crate::prover::inference::maximality::{literal_maximal_in, literal_strictly_maximal_in};
-use crate::prover::ordering::term_ordering::TermOrdering;
+use crate::prover::ordering::term_ordering::{Comparison, TermOrdering};
 use crate::prover::unification::full_unification::mgu;
 
+/// Replaces the subterm of u at the position given by the trace with t.
+/// Only the terms along the position are rebuilt, the rest are shared with u.
 fn create_overlapped_term(u: &Term, t: &Term, trace: &[usize]) -> Term {
-    let mut new_term = u.clone();
-    create_overlapped_term0(&mut new_term, t, trace, 0);
-    new_term
-}
-
-fn create_overlapped_term0(u_p: &mut Term, t: &Term, trace: &[usize], n: usize) {
-    if n >= trace.len() {
-        *u_p = t.clone();
+    if let Some((&i, rest)) = trace.split_first() {
+        assert_neq!(u.get_arity(), 0);
+        let mut args = u.iter().cloned().collect::<Vec<_>>();
+        args[i] = create_overlapped_term(&u[i], t, rest);
+        u.with_args(args)
     } else {
-        assert_neq!(u_p.get_arity(), 0);
-        create_overlapped_term0(&mut u_p[trace[n]], t, trace, n + 1)
+        t.clone()
     }
 }
 
 /// TODO: I really, REALLY should document and clean this up.
-#[cfg_attr(feature = "clippy", allow(too_many_arguments))]
+#[allow(clippy::too_many_arguments)]
 fn overlaps(
     term_ordering: &TermOrdering,
     s: &Term,
@@ -47,6 +47,7 @@ fn overlaps(
     u: &Term,
     v: &Term,
     u_v_negated: bool,
+    u_v_selected: bool,
     u_p: &Term,
     cl1: &Clause,
     cl1_i: usize,
@@ -76,7 +77,10 @@ fn overlaps(
                         new_d.swap_remove(cl2_i);
                         new_d.subst(&sigma);
 
-                        let maximality_condition_fulfilled = if u_v_negated {
+                        // Selected literals don't need to be maximal.
+                        let maximality_condition_fulfilled = if u_v_selected {
+                            true
+                        } else if u_v_negated {
                             literal_maximal_in(term_ordering, &new_d, &new_u_v)
                         } else {
                             literal_strictly_maximal_in(term_ordering, &new_d, &new_u_v)
@@ -87,6 +91,13 @@ fn overlaps(
                             new_lit.subst(&sigma);
                             new_c.add_literals(new_d);
                             new_c.add_literal(new_lit);
+                            new_c.set_derivation(Derivation::new_inference(
+                                InferenceRule::Superposition,
+                                &[cl1.get_id(), cl2.get_id()],
+                            ));
+                            new_c.set_from_conjecture(
+                                cl1.is_from_conjecture() || cl2.is_from_conjecture(),
+                            );
                             generated.push(new_c);
                             sp_count += 1;
                         }
@@ -104,6 +115,7 @@ fn overlaps(
                 u,
                 v,
                 u_v_negated,
+                u_v_selected,
                 x,
                 cl1,
                 cl1_i,
@@ -125,6 +137,7 @@ fn overlaps_literal(
     cl1_i: usize,
     cl2: &Clause,
     cl2_i: usize,
+    cl2_selected: bool,
     generated: &mut Vec<Clause>,
 ) -> usize {
     let mut trace = Vec::new();
@@ -143,6 +156,7 @@ fn overlaps_literal(
         r_lhs,
         r_rhs,
         r_negated,
+        cl2_selected,
         r_lhs,
         cl1,
         cl1_i,
@@ -158,6 +172,7 @@ fn overlaps_literal(
         r_lhs,
         r_rhs,
         r_negated,
+        cl2_selected,
         r_lhs,
         cl1,
         cl1_i,
@@ -173,6 +188,7 @@ fn overlaps_literal(
         r_rhs,
         r_lhs,
         r_negated,
+        cl2_selected,
         r_rhs,
         cl1,
         cl1_i,
@@ -188,6 +204,7 @@ fn overlaps_literal(
         r_rhs,
         r_lhs,
         r_negated,
+        cl2_selected,
         r_rhs,
         cl1,
         cl1_i,
@@ -201,19 +218,30 @@ fn overlaps_literal(
 }
 
 /// Generates superposition inferences between two clauses, in one direction.
+/// The clauses are given with the indices of their selected literals.
+/// Clauses with selected literals can only be superposed into, and only in the selected literals.
 /// Returns the amount of generated clauses.
 fn superposition_generate(
     term_ordering: &TermOrdering,
     cl1: &Clause,
+    cl1_selected: &[usize],
     cl2: &Clause,
+    cl2_selected: &[usize],
     generated: &mut Vec<Clause>,
 ) -> usize {
     let mut sp_count = 0;
+    if !cl1_selected.is_empty() {
+        return sp_count;
+    }
 
     for (i, l1) in cl1.iter().enumerate() {
         if l1.is_positive() {
             for j in 0..cl2.size() {
-                sp_count += overlaps_literal(term_ordering, cl1, i, cl2, j, generated);
+                if cl2_selected.is_empty() || cl2_selected.contains(&j) {
+                    let selected = !cl2_selected.is_empty();
+                    sp_count +=
+                        overlaps_literal(term_ordering, cl1, i, cl2, j, selected, generated);
+                }
             }
         }
     }
@@ -221,25 +249,192 @@ fn superposition_generate(
     sp_count
 }
 
+/// Get the sides of the literals of a clause which can be used for superposing into other clauses.
+/// Sides which are smaller than the other side cannot be used.
+pub fn from_terms<'a>(term_ordering: &TermOrdering, cl: &'a Clause) -> Vec<&'a Term> {
+    let mut terms = Vec::new();
+
+    for l in cl.iter().filter(|l| l.is_positive()) {
+        let comparison = term_ordering.compare(l.get_lhs(), l.get_rhs());
+        if comparison != Comparison::Less {
+            terms.push(l.get_lhs());
+        }
+        if comparison != Comparison::Greater {
+            terms.push(l.get_rhs());
+        }
+    }
+
+    terms
+}
+
+/// Get the non-variable subterms of a clause which other clauses can be superposed into.
+/// Subterms of sides which are smaller than the other side are not included.
+/// Since selected literals don't need to be maximal, every literal is considered.
+pub fn into_terms<'a>(term_ordering: &TermOrdering, cl: &'a Clause) -> Vec<&'a Term> {
+    let mut terms = Vec::new();
+
+    for l in cl.iter() {
+        let comparison = term_ordering.compare(l.get_lhs(), l.get_rhs());
+        if comparison != Comparison::Less {
+            add_subterms(l.get_lhs(), &mut terms);
+        }
+        if comparison != Comparison::Greater {
+            add_subterms(l.get_rhs(), &mut terms);
+        }
+    }
+
+    terms
+}
+
+/// Helper for above.
+fn add_subterms<'a>(t: &'a Term, terms: &mut Vec<&'a Term>) {
+    if !t.is_variable() {
+        terms.push(t);
+        for sub_t in t.iter() {
+            add_subterms(sub_t, terms);
+        }
+    }
+}
+
 /// Infers new clauses by positive and negative superposition.
 /// Time complexity is who the fuck knows.
 /// Assumes that cl was renamed so that it has no variables in common with any other clause.
 /// Returns the amount of clauses generated.
-pub fn superposition(
+pub fn superposition<'a, I>(
     term_ordering: &TermOrdering,
+    literal_selection: LiteralSelection,
     cl: &Clause,
-    clauses: &[Clause],
+    clauses: I,
     generated: &mut Vec<Clause>,
-) -> usize {
+) -> usize
+where
+    I: IntoIterator<Item = &'a Clause>,
+{
     let mut sp_count = 0;
+    let selected = literal_selection.select(cl);
 
     for cl2 in clauses {
-        sp_count += superposition_generate(term_ordering, cl, cl2, generated);
-        sp_count += superposition_generate(term_ordering, cl2, cl, generated);
+        let selected2 = literal_selection.select(cl2);
+        sp_count +=
+            superposition_generate(term_ordering, cl, &selected, cl2, &selected2, generated);
+        sp_count +=
+            superposition_generate(term_ordering, cl2, &selected2, cl, &selected, generated);
     }
 
     sp_count
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::superposition;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use crate::prover::inference::literal_selection::LiteralSelection;
+    use crate::prover::ordering::precedence::Precedence;
+    use crate::prover::ordering::term_ordering::TermOrdering;
+    use crate::prover::ordering::weight::Weight;
+
+    fn kbo() -> TermOrdering {
+        TermOrdering::KBO(Precedence::default(), Weight::SimpleWeight, None)
+    }
+
+    fn new_clause(id: u64, literals: Vec<Literal>) -> Clause {
+        let mut cl = Clause::new(literals);
+        cl.set_id(id);
+        cl
+    }
+
+    fn superpose(literal_selection: LiteralSelection, cl1: &Clause, cl2: &Clause) -> Vec<Clause> {
+        let mut generated = Vec::new();
+        let count = superposition(&kbo(), literal_selection, cl1, vec![cl2], &mut generated);
+        assert_eq!(count, generated.len());
+        generated
+    }
+
+    #[test]
+    fn superposition_not_from_selected() {
+        // f(a) = b | c <> d can't be used for superposing into g(f(a)) = e if c <> d is selected.
+        let a = Term::new_constant(1);
+        let b = Term::new_constant(2);
+        let c = Term::new_constant(3);
+        let d = Term::new_constant(4);
+        let e = Term::new_constant(5);
+        let f_a = Term::new_function(6, vec![a]);
+        let g_f_a = Term::new_function(7, vec![f_a.clone()]);
+        let cl1 = new_clause(
+            0,
+            vec![
+                Literal::new(false, f_a, b.clone()),
+                Literal::new(true, c, d),
+            ],
+        );
+        let cl2 = new_clause(1, vec![Literal::new(false, g_f_a, e.clone())]);
+        let g_b_e = Literal::new(false, Term::new_function(7, vec![b]), e);
+
+        let generated = superpose(LiteralSelection::None, &cl1, &cl2);
+        assert!(generated.iter().any(|cl| cl.iter().any(|l| *l == g_b_e)));
+        assert!(superpose(LiteralSelection::AllNegative, &cl1, &cl2).is_empty());
+    }
+
+    #[test]
+    fn superposition_only_into_selected() {
+        // a = b is superposed into f(a) = c | g(a) <> d only in the selected literal g(a) <> d.
+        let b = Term::new_constant(1);
+        let a = Term::new_constant(2);
+        let c = Term::new_constant(3);
+        let d = Term::new_constant(4);
+        let f_a = Term::new_function(6, vec![a.clone()]);
+        let g_a = Term::new_function(5, vec![a.clone()]);
+        let cl1 = new_clause(0, vec![Literal::new(false, a, b.clone())]);
+        let cl2 = new_clause(
+            1,
+            vec![
+                Literal::new(false, f_a.clone(), c.clone()),
+                Literal::new(true, g_a, d.clone()),
+            ],
+        );
+        let f_b_c = Literal::new(false, Term::new_function(6, vec![b.clone()]), c);
+        let g_b_d = Literal::new(true, Term::new_function(5, vec![b]), d);
+
+        let generated = superpose(LiteralSelection::AllNegative, &cl1, &cl2);
+        assert_eq!(generated.len(), 1);
+        assert!(generated[0].iter().any(|l| *l == g_b_d));
+        assert!(generated[0].iter().all(|l| *l != f_b_c));
+
+        let generated = superpose(LiteralSelection::None, &cl1, &cl2);
+        assert!(generated.iter().any(|cl| cl.iter().any(|l| *l == f_b_c)));
+    }
+
+    #[test]
+    fn superposition_selected_need_not_be_maximal() {
+        // a <> d is not maximal in h(h(h(a))) = c | a <> d, but it can be superposed into when selected.
+        let d = Term::new_constant(1);
+        let b = Term::new_constant(2);
+        let a = Term::new_constant(3);
+        let c = Term::new_constant(4);
+        let h_h_h_a = Term::new_function(
+            5,
+            vec![Term::new_function(
+                5,
+                vec![Term::new_function(5, vec![a.clone()])],
+            )],
+        );
+        let cl1 = new_clause(0, vec![Literal::new(false, a.clone(), b.clone())]);
+        let cl2 = new_clause(
+            1,
+            vec![
+                Literal::new(false, h_h_h_a, c),
+                Literal::new(true, a, d.clone()),
+            ],
+        );
+        let b_d = Literal::new(true, b, d);
+
+        let generated = superpose(LiteralSelection::AllNegative, &cl1, &cl2);
+        assert!(generated.iter().any(|cl| cl.iter().any(|l| *l == b_d)));
+
+        let generated = superpose(LiteralSelection::None, &cl1, &cl2);
+        assert!(!generated.is_empty());
+        assert!(generated.iter().all(|cl| cl.iter().all(|l| *l != b_d)));
+    }
+}
@@ -23,20 +23,20 @@ mod simplification;
 mod ordering;
 
 /// Contains all the inference rules used by the proof search.
-mod inference;
+pub mod inference;
 
 /// Contains stuff for performing both unification and matching.
 mod unification;
 
 /// Contains different (non-generic) data structures used by the proof search.
 /// Generic data structures should go to utils.
-mod data_structures;
+pub mod data_structures;
 
 /// Contains functions for automatically determining good prover settings for a given problem.
 mod problem_analysis;
 
 /// Contains functions for selecting given clauses to analyze in the proof search.
-mod clause_selection;
+pub mod clause_selection;
 
 // TODO: figure out a new name for this.
 // flatten is just not descriptive and it also means something else on top of that.
@@ -48,8 +48,23 @@ pub mod proof_result;
 /// Contains stuff on statistics collected during a proof search.
 pub mod proof_statistics;
 
+/// Contains cancellation and limits on the resources used by a proof search.
+pub mod resource_limits;
+
+/// Contains the SInE axiom selection for problems with large sets of axioms.
+pub mod axiom_selection;
+
 /// Contains stuff for keeping track of the current proof state during a proof search.
 mod proof_state;
 
+/// Contains functions for printing clauses and proofs in the TSTP format.
+mod tstp_output;
+
 /// Contains the core of the program, the proof search.
 pub mod proof_search;
+
+/// Contains a builder for configuring and running proof searches from other programs.
+pub mod builder;
+
+/// Contains schedules for trying several configurations of the prover on the same problem.
+pub mod schedule;
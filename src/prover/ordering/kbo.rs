@@ -16,8 +16,8 @@
 
 use crate::prover::data_structures::term::Term;
 use crate::prover::ordering::precedence::Precedence;
+use crate::prover::ordering::term_ordering::Comparison;
 use crate::prover::ordering::weight::Weight;
-use crate::utils::hash_map::HashMap;
 
 /// Checks if s is greater than t according to the ordering.
 pub fn kbo_gt(
@@ -27,28 +27,7 @@ pub fn kbo_gt(
     s: &Term,
     t: &Term,
 ) -> bool {
-    if s.is_function() && t.is_function() {
-        let s_weight = weight.weight(only_unary_func, s);
-        let t_weight = weight.weight(only_unary_func, t);
-        if s_weight > t_weight {
-            variable_domination(s, t)
-        } else if s_weight == t_weight {
-            if kbo_precedence(precedence, only_unary_func, s, t)
-                || (s.get_id() == t.get_id()
-                    && lexical_ordering(precedence, weight, only_unary_func, s, t))
-            {
-                variable_domination(s, t)
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    } else if s.is_function() && t.is_variable() {
-        s.occurs_proper(t)
-    } else {
-        false
-    }
+    kbo_compare(precedence, weight, only_unary_func, s, t) == Comparison::Greater
 }
 
 /// Checks if s is greater than or equal to t according to the ordering.
@@ -62,44 +41,165 @@ pub fn kbo_ge(
     s == t || kbo_gt(precedence, weight, only_unary_func, s, t)
 }
 
-/// Checks if for every variable x the amount of x in s is greater than or equal to the amount in t.
-fn variable_domination(s: &Term, t: &Term) -> bool {
-    let mut variable_counts = HashMap::default();
-    variable_count(&mut variable_counts, s, 1);
-    variable_count(&mut variable_counts, t, -1);
-    variable_counts.values().all(|&count| count >= 0)
-}
-
-fn variable_count(counts: &mut HashMap<i64, i64>, t: &Term, weight: i64) {
-    if t.is_variable() {
-        let v = counts.entry(t.get_id()).or_insert(0);
-        *v += weight;
-    } else {
-        for x in t.iter() {
-            variable_count(counts, x, weight);
-        }
-    }
-}
-
-fn lexical_ordering(
+/// Compares s and t according to the ordering.
+/// Based on the linear time algorithm in "Things to know when implementing KBO" by Bernd Löchner.
+/// Instead of recomputing the weights and variable counts of subterms during the lexicographic comparison,
+/// both terms are traversed only once while keeping track of the differences.
+/// The subterms which are not compared lexicographically are not traversed at all,
+/// since their weights and variable occurrences are cached.
+pub fn kbo_compare(
     precedence: &Precedence,
     weight: &Weight,
     only_unary_func: &Option<i64>,
     s: &Term,
     t: &Term,
-) -> bool {
-    assert_eq!(s.get_id(), t.get_id());
-    assert_eq!(s.get_arity(), t.get_arity());
+) -> Comparison {
+    let mut balance = Balance::new(weight, only_unary_func);
+    balance.compare(precedence, s, t)
+}
 
-    for i in 0..s.get_arity() {
-        if kbo_gt(precedence, weight, only_unary_func, &s[i], &t[i]) {
-            return true;
-        } else if s[i] != t[i] {
-            return false;
+/// Keeps track of the differences in weight and variable counts between the terms being compared.
+/// The differences are positive when the left term has more weight or more occurrences of a variable.
+struct Balance<'a> {
+    weight: &'a Weight,
+    only_unary_func: &'a Option<i64>,
+    weight_balance: i64,
+    // Terms contain only a few different variables, so a vector is faster than a hash map.
+    var_balance: Vec<(i64, i64)>,
+    pos_vars: usize,
+    neg_vars: usize,
+}
+
+impl<'a> Balance<'a> {
+    fn new(weight: &'a Weight, only_unary_func: &'a Option<i64>) -> Balance<'a> {
+        Balance {
+            weight,
+            only_unary_func,
+            weight_balance: 0,
+            var_balance: Vec::new(),
+            pos_vars: 0,
+            neg_vars: 0,
         }
     }
 
-    false
+    /// Get the balance of a variable.
+    fn var_balance_mut(&mut self, x: i64) -> &mut i64 {
+        let i = match self.var_balance.iter().position(|&(y, _)| x == y) {
+            Some(i) => i,
+            None => {
+                self.var_balance.push((x, 0));
+                self.var_balance.len() - 1
+            }
+        };
+        &mut self.var_balance[i].1
+    }
+
+    /// Records occurrences of a variable in the left term, or in the right term if the amount is negative.
+    fn add_var(&mut self, x: i64, n: i64) {
+        let b = self.var_balance_mut(x);
+        let old = *b;
+        *b += n;
+        let new = *b;
+        if old > 0 {
+            self.pos_vars -= 1;
+        } else if old < 0 {
+            self.neg_vars -= 1;
+        }
+        if new > 0 {
+            self.pos_vars += 1;
+        } else if new < 0 {
+            self.neg_vars += 1;
+        }
+    }
+
+    /// Adds the weight and the variables of a term to the balance of the left or the right term.
+    /// Returns true if the given variable occurs in the term.
+    fn add_term(&mut self, t: &Term, left: bool, var: Option<i64>) -> bool {
+        let sign = if left { 1 } else { -1 };
+        self.weight_balance += sign * self.weight.weight(self.only_unary_func, t) as i64;
+        for &(x, n) in t.get_variables() {
+            self.add_var(x, sign * n as i64);
+        }
+        var.is_some_and(|x| t.has_variable(x))
+    }
+
+    /// Compares s and t while adding them to the balance.
+    fn compare(&mut self, precedence: &Precedence, s: &Term, t: &Term) -> Comparison {
+        if s.is_variable() {
+            self.add_term(s, true, None);
+            let occurs = self.add_term(t, false, Some(s.get_id()));
+            if s == t {
+                Comparison::Equal
+            } else if occurs {
+                Comparison::Less
+            } else {
+                Comparison::Incomparable
+            }
+        } else if t.is_variable() {
+            let occurs = self.add_term(s, true, Some(t.get_id()));
+            self.add_term(t, false, None);
+            if occurs {
+                Comparison::Greater
+            } else {
+                Comparison::Incomparable
+            }
+        } else {
+            let lex = if s.get_id() == t.get_id() {
+                assert_eq!(s.get_arity(), t.get_arity());
+                // Only the first pair of arguments which are not equal matter lexicographically.
+                let mut lex = Comparison::Equal;
+                for (s_i, t_i) in s.iter().zip(t.iter()) {
+                    if lex == Comparison::Equal {
+                        lex = self.compare(precedence, s_i, t_i);
+                    } else {
+                        self.add_term(s_i, true, None);
+                        self.add_term(t_i, false, None);
+                    }
+                }
+                lex
+            } else {
+                for s_i in s.iter() {
+                    self.add_term(s_i, true, None);
+                }
+                for t_i in t.iter() {
+                    self.add_term(t_i, false, None);
+                }
+                if kbo_precedence(precedence, self.only_unary_func, s, t) {
+                    Comparison::Greater
+                } else if kbo_precedence(precedence, self.only_unary_func, t, s) {
+                    Comparison::Less
+                } else {
+                    Comparison::Incomparable
+                }
+            };
+            self.weight_balance += self.weight.symbol_weight(self.only_unary_func, s) as i64;
+            self.weight_balance -= self.weight.symbol_weight(self.only_unary_func, t) as i64;
+
+            // The variable condition has to hold in any case.
+            let greater_allowed = self.neg_vars == 0;
+            let less_allowed = self.pos_vars == 0;
+            if self.weight_balance > 0 {
+                if greater_allowed {
+                    Comparison::Greater
+                } else {
+                    Comparison::Incomparable
+                }
+            } else if self.weight_balance < 0 {
+                if less_allowed {
+                    Comparison::Less
+                } else {
+                    Comparison::Incomparable
+                }
+            } else {
+                match lex {
+                    Comparison::Greater if greater_allowed => Comparison::Greater,
+                    Comparison::Less if less_allowed => Comparison::Less,
+                    Comparison::Equal => Comparison::Equal,
+                    _ => Comparison::Incomparable,
+                }
+            }
+        }
+    }
 }
 
 /// Expands the precedence so that it is suitable for KBO.
@@ -123,10 +223,106 @@ fn kbo_precedence(
 
 #[cfg(test)]
 mod test {
-    use super::kbo_gt;
+    use super::{kbo_compare, kbo_gt, kbo_precedence};
     use crate::prover::data_structures::term::Term;
     use crate::prover::ordering::precedence::Precedence;
+    use crate::prover::ordering::term_ordering::Comparison;
     use crate::prover::ordering::weight::Weight;
+    use crate::utils::hash_map::HashMap;
+
+    /// The textbook definition of KBO, used for checking the optimized version.
+    fn kbo_gt_reference(only_unary_func: &Option<i64>, s: &Term, t: &Term) -> bool {
+        let precedence = Precedence::default();
+        let weight = Weight::SimpleWeight;
+        if s.is_function() && t.is_function() {
+            let s_weight = weight.weight(only_unary_func, s);
+            let t_weight = weight.weight(only_unary_func, t);
+            let lexical = s.get_id() == t.get_id() && {
+                let i = (0..s.get_arity()).find(|&i| s[i] != t[i]);
+                i.is_some_and(|i| kbo_gt_reference(only_unary_func, &s[i], &t[i]))
+            };
+            let greater = s_weight > t_weight
+                || (s_weight == t_weight
+                    && (kbo_precedence(&precedence, only_unary_func, s, t) || lexical));
+            greater && variable_domination(s, t)
+        } else if s.is_function() && t.is_variable() {
+            s.occurs_proper(t)
+        } else {
+            false
+        }
+    }
+
+    fn variable_domination(s: &Term, t: &Term) -> bool {
+        let mut counts = HashMap::default();
+        variable_count(&mut counts, s, 1);
+        variable_count(&mut counts, t, -1);
+        counts.values().all(|&count| count >= 0)
+    }
+
+    fn variable_count(counts: &mut HashMap<i64, i64>, t: &Term, weight: i64) {
+        if t.is_variable() {
+            *counts.entry(t.get_id()).or_insert(0) += weight;
+        } else {
+            for x in t.iter() {
+                variable_count(counts, x, weight);
+            }
+        }
+    }
+
+    /// A simple xorshift generator so that the randomized test is reproducible.
+    fn next(state: &mut u64, n: u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state % n
+    }
+
+    /// Generates a random term over the constants a and b, the functions f/1 and g/2,
+    /// and the variables x and y.
+    fn random_term(state: &mut u64, depth: usize) -> Term {
+        let choice = if depth == 0 {
+            next(state, 4)
+        } else {
+            next(state, 7)
+        };
+        match choice {
+            0 => Term::new_constant(1),
+            1 => Term::new_constant(2),
+            2 | 3 => Term::new_variable(-(choice as i64) + 1),
+            4 | 5 => Term::new_function(3, vec![random_term(state, depth - 1)]),
+            _ => Term::new_function(
+                4,
+                vec![random_term(state, depth - 1), random_term(state, depth - 1)],
+            ),
+        }
+    }
+
+    #[test]
+    fn kbo_compare_random() {
+        let precedence = Precedence::default();
+        let weight = Weight::SimpleWeight;
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+
+        for only_unary_func in &[None, Some(3)] {
+            for _ in 0..5000 {
+                let s = random_term(&mut state, 4);
+                let t = random_term(&mut state, 4);
+                let expected = if s == t {
+                    Comparison::Equal
+                } else if kbo_gt_reference(only_unary_func, &s, &t) {
+                    Comparison::Greater
+                } else if kbo_gt_reference(only_unary_func, &t, &s) {
+                    Comparison::Less
+                } else {
+                    Comparison::Incomparable
+                };
+                assert_eq!(
+                    kbo_compare(&precedence, &weight, only_unary_func, &s, &t),
+                    expected
+                );
+            }
+        }
+    }
 
     #[test]
     fn kbo_gt_1() {
@@ -164,6 +360,21 @@ mod test {
         ));
     }
 
+    #[test]
+    fn kbo_gt_distinct_object() {
+        let precedence = Precedence::default();
+        let weight = Weight::SimpleWeight;
+        let only_unary_func = Some(3);
+
+        let d = Term::new_distinct_object(1);
+        let c = Term::new_constant(2);
+        let f_c = Term::new_function(3, vec![c.clone()]);
+        assert!(kbo_gt(&precedence, &weight, &only_unary_func, &c, &d));
+        assert!(!kbo_gt(&precedence, &weight, &only_unary_func, &d, &c));
+        assert!(kbo_gt(&precedence, &weight, &only_unary_func, &f_c, &d));
+        assert!(!kbo_gt(&precedence, &weight, &only_unary_func, &d, &f_c));
+    }
+
     #[test]
     fn kbo_gt_3() {
         let precedence = Precedence::default();
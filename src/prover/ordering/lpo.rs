@@ -123,6 +123,19 @@ mod test {
         assert!(!lpo_gt(&precedence, &t, &f_x_x));
     }
 
+    #[test]
+    fn lpo_gt_distinct_object() {
+        let precedence = Precedence::default();
+        let d = Term::new_distinct_object(1);
+        let c = Term::new_constant(2);
+        let x = Term::new_variable(-1);
+        let f_x = Term::new_function(3, vec![x]);
+        assert!(lpo_gt(&precedence, &c, &d));
+        assert!(!lpo_gt(&precedence, &d, &c));
+        assert!(lpo_gt(&precedence, &f_x, &d));
+        assert!(!lpo_gt(&precedence, &d, &f_x));
+    }
+
     #[test]
     fn lpo_ge_1() {
         let precedence = Precedence::default();
@@ -16,13 +16,13 @@
 
 use crate::prover::data_structures::term::Term;
 use crate::utils::hash_map::HashMap;
-use std::default::Default;
 
 /// Defines a partial orders on the function symbols.
 /// Different enums represent different partial orders.
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum Precedence {
+    #[default]
     ArityId,
     ArityFrequency(HashMap<i64, i64>),
     Frequency(HashMap<i64, i64>),
@@ -32,10 +32,17 @@ pub enum Precedence {
 
 impl Precedence {
     /// Checks if s has precedence over t.
+    /// Distinct objects are below all other function symbols.
+    /// Since they have the minimal weight, this makes them smaller than every other non-variable term
+    /// in both KBO and LPO, so that equations between them and other terms are always oriented towards them.
     pub fn gt(&self, s: &Term, t: &Term) -> bool {
         assert!(s.is_function());
         assert!(t.is_function());
 
+        if s.is_distinct_object() != t.is_distinct_object() {
+            return t.is_distinct_object();
+        }
+
         match *self {
             Precedence::ArityId => arity_id_gt(s, t),
             Precedence::ArityFrequency(ref frequency_table) => {
@@ -48,12 +55,6 @@ impl Precedence {
     }
 }
 
-impl Default for Precedence {
-    fn default() -> Precedence {
-        Precedence::ArityId
-    }
-}
-
 /// Orders function symbols first based on arity and then by ID number.
 fn arity_id_gt(s: &Term, t: &Term) -> bool {
     if s.get_arity() == t.get_arity() {
@@ -73,13 +74,11 @@ fn arity_frequency_gt(frequency_table: &HashMap<i64, i64>, s: &Term, t: &Term) -
 }
 
 /// Orders function symbols based on their rarity according to some count table.
+/// Symbols created during the search, such as computed numbers, are not in the table.
+/// They are treated as the most common ones so that they are as small as possible.
 fn frequency_gt(frequency_table: &HashMap<i64, i64>, s: &Term, t: &Term) -> bool {
-    let s_freq = frequency_table
-        .get(&s.get_id())
-        .expect("Symbol not found in frequency table");
-    let t_freq = frequency_table
-        .get(&t.get_id())
-        .expect("Symbol not found in frequency table");
+    let s_freq = frequency_table.get(&s.get_id()).unwrap_or(&i64::MAX);
+    let t_freq = frequency_table.get(&t.get_id()).unwrap_or(&i64::MAX);
     s_freq < t_freq
 }
 
@@ -122,4 +121,19 @@ mod test {
         assert!(precedence.gt(&c1, &t));
         assert!(precedence.gt(&c2, &t));
     }
+
+    #[test]
+    fn distinct_objects_are_smallest() {
+        let precedence = Precedence::ArityId;
+        let t = Term::new_truth();
+        let c = Term::new_function(1, Vec::new());
+        let d1 = Term::new_distinct_object(2);
+        let d2 = Term::new_distinct_object(3);
+        assert!(precedence.gt(&c, &d1));
+        assert!(!precedence.gt(&d1, &c));
+        assert!(precedence.gt(&t, &d2));
+        assert!(!precedence.gt(&d2, &t));
+        assert!(precedence.gt(&d2, &d1));
+        assert!(!precedence.gt(&d1, &d2));
+    }
 }
@@ -16,14 +16,24 @@
 
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::data_structures::term::Term;
-use crate::prover::ordering::kbo::{kbo_ge, kbo_gt};
+use crate::prover::ordering::kbo::{kbo_compare, kbo_ge, kbo_gt};
 use crate::prover::ordering::lpo::{lpo_ge, lpo_gt};
 use crate::prover::ordering::precedence::Precedence;
 use crate::prover::ordering::weight::Weight;
 use std::cmp::min;
 
+/// The result of comparing two terms with a term ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    Less,
+    Equal,
+    Incomparable,
+}
+
 /// A generic term ordering. Currently we have the option of using either LPO or KBO.
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum TermOrdering {
     LPO(Precedence),
     KBO(Precedence, Weight, Option<i64>),
@@ -51,6 +61,27 @@ impl TermOrdering {
         }
     }
 
+    /// Compares s and t according to the term ordering.
+    /// This is faster than checking both s > t and t > s separately.
+    pub fn compare(&self, s: &Term, t: &Term) -> Comparison {
+        match *self {
+            TermOrdering::LPO(ref precedence) => {
+                if s == t {
+                    Comparison::Equal
+                } else if lpo_gt(precedence, s, t) {
+                    Comparison::Greater
+                } else if lpo_gt(precedence, t, s) {
+                    Comparison::Less
+                } else {
+                    Comparison::Incomparable
+                }
+            }
+            TermOrdering::KBO(ref precedence, ref weight, only_unary_func) => {
+                kbo_compare(precedence, weight, &only_unary_func, s, t)
+            }
+        }
+    }
+
     /// Checks if literal l1 is greater than literal l2.
     /// This is based on the multiset extension of the term ordering.
     pub fn gt_lit(&self, l1: &Literal, l2: &Literal) -> bool {
@@ -137,7 +168,6 @@ fn multiset_difference(l: &Literal, r: &Literal) -> [usize; 2] {
     if r.get_rhs() == l.get_rhs() {
         let min_diff = min(l_count[1], r_count[1]);
         l_count[1] -= min_diff;
-        r_count[1] -= min_diff;
     }
 
     l_count
@@ -30,6 +30,19 @@ impl Weight {
             Weight::SimpleWeight => simple_weight(only_unary_func, t),
         }
     }
+
+    /// Calculates the weight of the top symbol of t, ignoring its arguments.
+    pub fn symbol_weight(&self, only_unary_func: &Option<i64>, t: &Term) -> usize {
+        match *self {
+            Weight::SimpleWeight => {
+                if t.is_variable() {
+                    1
+                } else {
+                    function_symbol_weight(only_unary_func, t)
+                }
+            }
+        }
+    }
 }
 
 /// Variables have weight 1.
@@ -37,15 +50,10 @@ impl Weight {
 /// All other function symbols have weight 1.
 /// The weight function is extended to terms in the obvious way.
 /// That is, weight(f(t1, ..., tn)) = weight(f) + weight(t1) + weight(...) + weight(tn).
+/// The symbol counts of terms are cached, so this usually takes constant time.
 fn simple_weight(only_unary_func: &Option<i64>, t: &Term) -> usize {
-    if t.is_variable() {
-        1
-    } else {
-        let func_symbol_weight = function_symbol_weight(only_unary_func, t);
-        t.iter().fold(func_symbol_weight, |acc, s| {
-            acc + simple_weight(only_unary_func, s)
-        })
-    }
+    let unary_count = only_unary_func.map_or(0, |id| t.unary_function_count(id));
+    (t.symbol_count(1, 1) - unary_count) as usize
 }
 
 /// Just a convenience function.
@@ -58,4 +66,25 @@ fn function_symbol_weight(only_unary_func: &Option<i64>, t: &Term) -> usize {
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::Weight;
+    use crate::prover::data_structures::term::Term;
+
+    #[test]
+    fn simple_weight_1() {
+        let weight = Weight::SimpleWeight;
+        let x = Term::new_variable(-1);
+        let a = Term::new_constant(1);
+        let f_x = Term::new_function(2, vec![x.clone()]);
+        let f_f_x = Term::new_function(2, vec![f_x.clone()]);
+        let g_f_f_x_a = Term::new_function(3, vec![f_f_x.clone(), a.clone()]);
+        let h_f_f_x = Term::new_function(4, vec![f_f_x.clone()]);
+
+        assert_eq!(weight.weight(&None, &g_f_f_x_a), 5);
+        assert_eq!(weight.weight(&Some(2), &g_f_f_x_a), 3);
+        assert_eq!(weight.weight(&Some(4), &g_f_f_x_a), 5);
+        // With several unary functions the occurrences are not cached.
+        assert_eq!(weight.weight(&Some(2), &h_f_f_x), 2);
+        assert_eq!(weight.weight(&Some(4), &h_f_f_x), 3);
+    }
+}
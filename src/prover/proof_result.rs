@@ -22,7 +22,11 @@ pub enum ProofResult {
     CounterSatisfiable,
     Unsatisfiable,
     Satisfiable,
+    GaveUp,
     Timeout,
+    ResourceOut,
+    SyntaxError(String),
+    InputError(String),
     Error(String),
 }
 
@@ -53,25 +57,57 @@ impl ProofResult {
             ProofResult::CounterSatisfiable => "CounterSatisfiable".to_owned(),
             ProofResult::Unsatisfiable => "Unsatisfiable".to_owned(),
             ProofResult::Satisfiable => "Satisfiable".to_owned(),
+            ProofResult::GaveUp => "GaveUp".to_owned(),
             ProofResult::Timeout => "Timeout".to_owned(),
+            ProofResult::ResourceOut => "ResourceOut".to_owned(),
+            ProofResult::SyntaxError(_) => "SyntaxError".to_owned(),
+            ProofResult::InputError(_) => "InputError".to_owned(),
             ProofResult::Error(_) => "Error".to_owned(),
         }
     }
 
     /// Is the current result successful (in the sense that a proof of some kind was found)?
     pub fn is_successful(&self) -> bool {
+        !matches!(
+            *self,
+            ProofResult::GaveUp
+                | ProofResult::Timeout
+                | ProofResult::ResourceOut
+                | ProofResult::SyntaxError(_)
+                | ProofResult::InputError(_)
+                | ProofResult::Error(_)
+        )
+    }
+
+    /// Was the result obtained by deriving a contradiction?
+    pub fn is_refutation(&self) -> bool {
+        matches!(*self, ProofResult::Theorem | ProofResult::Unsatisfiable)
+    }
+
+    /// Was the result obtained by saturating the clause set?
+    pub fn is_saturation(&self) -> bool {
+        matches!(
+            *self,
+            ProofResult::CounterSatisfiable | ProofResult::Satisfiable
+        )
+    }
+
+    /// Get the message describing the error, if the result is an error.
+    pub fn get_error_message(&self) -> Option<&str> {
         match *self {
-            ProofResult::Timeout | ProofResult::Error(_) => false,
-            _ => true,
+            ProofResult::SyntaxError(ref s)
+            | ProofResult::InputError(ref s)
+            | ProofResult::Error(ref s) => Some(s),
+            _ => None,
         }
     }
 
     /// Is the current result an error?
     pub fn is_err(&self) -> bool {
-        match *self {
-            ProofResult::Error(_) => true,
-            _ => false,
-        }
+        matches!(
+            *self,
+            ProofResult::SyntaxError(_) | ProofResult::InputError(_) | ProofResult::Error(_)
+        )
     }
 }
 
@@ -14,13 +14,25 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::prover::axiom_selection::{sine_select, AxiomSelection};
 use crate::prover::flatten_cnf::flatten_cnf;
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::Derivation;
+use crate::prover::data_structures::pd_tree::PDTree;
 
+use crate::prover::builder::Prover;
 use crate::prover::proof_state::ProofState;
-use crate::prover::proof_statistics::*;
-
+use crate::prover::proof_statistics::ProofStatistics;
+use crate::prover::tstp_output::{
+    origins_to_unsat_core, refutation_to_tstp, refutation_to_unsat_core, saturation_to_tstp,
+    trivial_refutation_to_tstp,
+};
+
+use crate::prover::simplification::arithmetic::{evaluate_arithmetic, Arithmetic};
+use crate::prover::simplification::distinct_objects::{
+    delete_distinct_object_equations, distinct_objects_unequal,
+};
 use crate::prover::simplification::equality_subsumption::forward_equality_subsumed;
 use crate::prover::simplification::literal_deletion::*;
 use crate::prover::simplification::non_unit_subsumption::non_unit_subsumed;
@@ -35,11 +47,15 @@ use crate::prover::inference::superposition::superposition;
 
 use crate::prover::problem_analysis::determine_term_ordering::create_term_ordering;
 
-use crate::utils::hash_map::HashMap;
+use crate::utils::hash_map::{HashMap, HashSet};
 
 use crate::cnf::ast::Formula;
-use crate::cnf::ast_transformer::tptp_to_cnf_ast;
+use crate::cnf::ast_transformer::{tptp_to_cnf_ast, CnfProblem};
+use crate::cnf::renaming_info::RenamingInfo;
 use crate::cnf::standard_cnf::cnf;
+use crate::tptp_parser::include_resolver::FileSystemResolver;
+use crate::tptp_parser::parse_error::TptpParseError;
+use std::iter;
 
 /// Rename a clause so that it contains no variables in common with any other clause.
 fn rename_clause(cl: &mut Clause, var_cnt: &mut i64) {
@@ -54,12 +70,67 @@ fn rename_clause(cl: &mut Clause, var_cnt: &mut i64) {
 fn forward_subsumed(proof_state: &ProofState, cl: &Clause) -> bool {
     forward_equality_subsumed(proof_state.get_term_index(), cl)
         || unit_subsumed(proof_state.get_term_index(), cl)
-        || non_unit_subsumed(proof_state.get_used(), cl)
+        || non_unit_subsumed(proof_state.get_possible_subsumers(cl), cl)
+}
+
+/// Removes the used clauses which are subsumed or can be rewritten by a given clause.
+/// The rewritten clauses are added back to the set of unused clauses.
+fn backward_simplify(proof_state: &mut ProofState, stats: &ProofStatistics, cl: &Clause) {
+    let mut redundant = HashSet::default();
+    let mut rewritten = Vec::new();
+
+    if cl.is_unit() {
+        let mut index = PDTree::new();
+        index.add_clause_to_index(proof_state.get_term_ordering(), cl);
+        let demodulator = cl[0].is_positive();
+
+        // Any clause the unit clause can subsume or rewrite contains an instance of one of its sides.
+        // If both sides are variables, every clause is a candidate.
+        let sides = [cl[0].get_lhs(), cl[0].get_rhs()]
+            .iter()
+            .cloned()
+            .filter(|t| !t.is_variable())
+            .collect::<Vec<_>>();
+        let candidates = if sides.is_empty() {
+            proof_state.get_used()
+        } else {
+            proof_state.get_clauses_with_instances(&sides).collect()
+        };
+
+        for used_cl in candidates {
+            if forward_equality_subsumed(&index, used_cl) || unit_subsumed(&index, used_cl) {
+                redundant.insert(used_cl.get_id());
+            } else if demodulator {
+                let mut new_cl = used_cl.clone();
+                // The rewritten clause gets a new ID, so it is derived from the used clause.
+                new_cl.set_derivation(Derivation::Clause(used_cl.get_id()));
+                if rewrite_clause(proof_state.get_term_ordering(), &index, &mut new_cl) {
+                    redundant.insert(used_cl.get_id());
+                    rewritten.push(new_cl);
+                }
+            }
+        }
+    } else {
+        for used_cl in proof_state.get_possibly_subsumed(cl) {
+            if non_unit_subsumed(iter::once(cl), used_cl) {
+                redundant.insert(used_cl.get_id());
+            }
+        }
+    }
+
+    if !redundant.is_empty() {
+        stats.add_backward_subsumed_count(redundant.len() - rewritten.len());
+        stats.add_backward_rewritten_count(rewritten.len());
+        proof_state.remove_from_used(&redundant);
+        for new_cl in rewritten {
+            handle_new_clause(proof_state, stats, new_cl);
+        }
+    }
 }
 
 /// Checks if a clause is a syntactical tautology.
 fn trivial(cl: &Clause) -> bool {
-    td1(cl) || td2(cl)
+    td1(cl) || td2(cl) || distinct_objects_unequal(cl)
 }
 
 /// Simplifies a clause with cheap (i.e. fast to run) rules if possible.
@@ -67,655 +138,1084 @@ fn cheap_simplify(cl: &mut Clause) {
     destructive_equality_resolution(cl);
     delete_resolved(cl);
     delete_duplicates(cl);
+    delete_distinct_object_equations(cl);
 }
 
 /// A more expensive version of `cheap_simplify` with more effective rules.
-fn simplify(proof_state: &ProofState, cl: &mut Clause) {
+fn simplify(proof_state: &mut ProofState, cl: &mut Clause) {
     rewrite_clause(
         proof_state.get_term_ordering(),
         proof_state.get_term_index(),
         cl,
     );
+    evaluate_arithmetic(proof_state.get_arithmetic_mut(), cl);
     cheap_simplify(cl);
     simplify_reflect(proof_state.get_term_index(), cl);
 }
 
 /// Simplifies a new clause, and adds it to the set of unused clauses if it is not trivial.
-fn handle_new_clause(proof_state: &mut ProofState, mut cl: Clause) {
+fn handle_new_clause(proof_state: &mut ProofState, stats: &ProofStatistics, mut cl: Clause) {
     // Simplification need to be done before triviality checking.
     // Consider the clause x <> y, y <> z, x = z which is clearly a tautology.
     // We cannot detect it as a tautology with a pure syntactical check,
     // unless we first simplify it with destructive equality resolution.
+    evaluate_arithmetic(proof_state.get_arithmetic_mut(), &mut cl);
     cheap_simplify(&mut cl);
     if trivial(&cl) {
-        increment_trivial_inference_count();
+        stats.increment_trivial_inference_count();
     } else {
         proof_state.add_to_unused(cl);
     }
 }
 
+/// The different ways the main proof search loop can end.
+enum SearchOutcome {
+    Refutation(Clause),
+    Saturation,
+    Stopped,
+}
+
+/// Checks if the search has been cancelled or if it has used up its resources.
+/// Also records the reason for stopping.
+fn search_should_stop(proof_state: &ProofState, options: &Prover, stats: &ProofStatistics) -> bool {
+    if options.get_cancellation_token().is_cancelled() {
+        stats.search_was_cancelled();
+        true
+    } else if options.get_resource_limits().exhausted(
+        stats.get_iteration_count(),
+        stats.get_inferred_count(),
+        proof_state.get_memory_estimate(),
+    ) {
+        stats.resources_were_exhausted();
+        true
+    } else {
+        false
+    }
+}
+
 /// The main proof search loop.
 /// Note that we use the DISCOUNT version of the given clause algorithm.
-/// Also note that this function might NEVER terminate, time handling should be done elsewhere.
-fn serkr_loop(mut proof_state: ProofState, mut var_cnt: i64) {
+/// The search is stopped if it is cancelled or if it exceeds the resource limits given.
+/// Otherwise this function might NEVER terminate.
+/// With the set of support restriction clauses which don't come from the negated conjecture
+/// are only used in inferences with clauses which do.
+fn serkr_loop(
+    proof_state: &mut ProofState,
+    options: &Prover,
+    stats: &ProofStatistics,
+    mut var_cnt: i64,
+    set_of_support: bool,
+) -> SearchOutcome {
     assert_eq!(proof_state.get_used_size(), 0);
-    set_initial_clauses(proof_state.get_unused_size());
+    stats.set_initial_clauses(proof_state.get_unused_size());
 
     // Pick the "best" clause from the set of unused clause.
     // Every clause should eventually be picked, otherwise system is not complete.
-    while let Some(mut chosen_clause) = proof_state.pick_best_clause() {
-        increment_iteration_count();
+    while !search_should_stop(proof_state, options, stats) {
+        let mut chosen_clause = match proof_state.pick_best_clause() {
+            Some(cl) => cl,
+            None => return SearchOutcome::Saturation,
+        };
+        stats.increment_iteration_count();
 
         // We start processing the chosen clause by simplifying it as much as possible.
-        simplify(&proof_state, &mut chosen_clause);
+        simplify(proof_state, &mut chosen_clause);
 
         // If we derived a contradiction we are done.
         if chosen_clause.is_empty() {
-            refutation_was_found();
-            return;
+            stats.refutation_was_found();
+            return SearchOutcome::Refutation(chosen_clause);
         }
 
         // Check if the clause is redundant in some way. If it is no need to process it more.
         if trivial(&chosen_clause) {
-            increment_trivial_count()
-        } else if forward_subsumed(&proof_state, &chosen_clause) {
-            increment_forward_subsumed_count();
+            stats.increment_trivial_count()
+        } else if forward_subsumed(proof_state, &chosen_clause) {
+            stats.increment_forward_subsumed_count();
         } else {
-            // The chosen clause wasn't redundant, so we add it to the set of used clauses.
+            // The chosen clause wasn't redundant, so we use it to simplify the used clauses
+            // and then add it to the set of used clauses.
+            backward_simplify(proof_state, stats, &chosen_clause);
             proof_state.add_to_used(chosen_clause.clone());
             // If we do not rename variables in the clause prior to inference we are in trouble.
             rename_clause(&mut chosen_clause, &mut var_cnt);
 
             let mut inferred_clauses = Vec::new();
+            // Clauses outside the set of support are only used with clauses inside it.
+            let restricted = set_of_support && !chosen_clause.is_from_conjecture();
             // Now perform all inferences between our chosen clause and used clauses.
             // This includes inferences with itself.
-            let sp_count = superposition(
-                proof_state.get_term_ordering(),
-                &chosen_clause,
-                proof_state.get_used(),
-                &mut inferred_clauses,
-            );
-            let ef_count = equality_factoring(
-                proof_state.get_term_ordering(),
-                &chosen_clause,
-                &mut inferred_clauses,
-            );
-            let er_count = equality_resolution(
-                proof_state.get_term_ordering(),
-                &chosen_clause,
-                &mut inferred_clauses,
-            );
-            add_superposition_inferred_count(sp_count);
-            add_equality_factoring_inferred_count(ef_count);
-            add_equality_resolution_inferred_count(er_count);
+            let sp_count = if restricted {
+                superposition(
+                    proof_state.get_term_ordering(),
+                    options.get_literal_selection(),
+                    &chosen_clause,
+                    proof_state
+                        .get_superposition_partners(&chosen_clause)
+                        .filter(|cl| cl.is_from_conjecture()),
+                    &mut inferred_clauses,
+                )
+            } else {
+                superposition(
+                    proof_state.get_term_ordering(),
+                    options.get_literal_selection(),
+                    &chosen_clause,
+                    proof_state.get_superposition_partners(&chosen_clause),
+                    &mut inferred_clauses,
+                )
+            };
+            let (ef_count, er_count) = if restricted {
+                (0, 0)
+            } else {
+                (
+                    equality_factoring(
+                        proof_state.get_term_ordering(),
+                        options.get_literal_selection(),
+                        &chosen_clause,
+                        &mut inferred_clauses,
+                    ),
+                    equality_resolution(
+                        proof_state.get_term_ordering(),
+                        options.get_literal_selection(),
+                        &chosen_clause,
+                        &mut inferred_clauses,
+                    ),
+                )
+            };
+            stats.add_superposition_inferred_count(sp_count);
+            stats.add_equality_factoring_inferred_count(ef_count);
+            stats.add_equality_resolution_inferred_count(er_count);
 
             // Finally handle all generated clauses.
             for cl in inferred_clauses {
-                handle_new_clause(&mut proof_state, cl);
+                handle_new_clause(proof_state, stats, cl);
             }
         }
     }
+
+    SearchOutcome::Stopped
 }
 
 /// Attempts to prove the stuff in the TPTP file at the location given.
 /// First we can decide whether we want to use LPO or KBO.
 /// Then there is the option for the renaming limit for CNF translation.
+/// The statistics and the result of the search are stored in the given `ProofStatistics`.
 /// Note that this function might NEVER terminate, time handling should be done outside this.
+pub fn prove(s: &str, use_lpo: bool, renaming_limit: u64, stats: &ProofStatistics) {
+    let options = Prover::new()
+        .use_lpo(use_lpo)
+        .renaming_limit(renaming_limit);
+    prove_tptp(
+        tptp_to_cnf_ast(s, &FileSystemResolver::new()),
+        s,
+        &options,
+        stats,
+    );
+}
+
+/// Attempts to prove a parsed TPTP problem with the given options.
+/// The formulas are paired with the IDs of their origins in the renaming info.
+/// The input name is only used for referring to the problem in the proof output.
 #[cfg_attr(feature = "clippy", allow(single_match_else))]
-pub fn prove(s: &str, use_lpo: bool, renaming_limit: u64) {
-    reset_statistics();
-    // First we obviously need to parse the file.
-    let (mut axioms, conjectures, mut renaming_info) = match tptp_to_cnf_ast(s) {
+pub fn prove_tptp(
+    parsed: Result<CnfProblem, TptpParseError>,
+    input_name: &str,
+    options: &Prover,
+    stats: &ProofStatistics,
+) {
+    let (axioms, conjectures, mut renaming_info) = match parsed {
         Ok(res) => res,
-        Err(_) => {
-            search_has_finished();
+        Err(e) => {
+            stats.set_parse_error(e);
+            stats.search_has_finished();
             return;
         }
     };
-    set_parsing_finished();
-    set_contains_conjectures(!conjectures.is_empty());
-
-    // Choose the correct form for the combination of axioms and conjectures.
-    let f = if !axioms.is_empty() && !conjectures.is_empty() {
-        axioms.push(Formula::Not(Box::new(Formula::And(conjectures))));
-        Formula::And(axioms)
-    } else if !axioms.is_empty() {
-        Formula::And(axioms)
-    } else if conjectures.is_empty() {
-        panic!("We should always have some clauses");
-    } else {
-        Formula::And(vec![Formula::Not(Box::new(Formula::And(conjectures)))])
-    };
-    // And finally transform the whole thing into CNF.
-    let cnf_f = cnf(f, &mut renaming_info, renaming_limit);
+    stats.set_parsing_finished();
+    stats.set_contains_conjectures(!conjectures.is_empty());
+
+    let axioms = select_axioms(axioms, &conjectures, &renaming_info, options, stats);
+
+    // Each formula is transformed into CNF separately so that the clauses know where they came from.
+    // The conjectures are negated together, so their clauses come from all of them.
+    let mut inputs = axioms
+        .into_iter()
+        .map(|(f, id)| (f, vec![id]))
+        .collect::<Vec<_>>();
+    if !conjectures.is_empty() {
+        let (conjectures, ids): (Vec<_>, Vec<_>) = conjectures.into_iter().unzip();
+        inputs.push((Formula::Not(Box::new(Formula::And(conjectures))), ids));
+    }
+
+    let mut clauses = Vec::new();
+    for (f, origins) in inputs {
+        match cnf(f, &mut renaming_info, options.get_renaming_limit()) {
+            Formula::False => {
+                stats.set_proof_output(trivial_refutation_to_tstp(
+                    &renaming_info,
+                    &origins,
+                    input_name,
+                ));
+                stats.set_unsat_core(origins_to_unsat_core(&renaming_info, origins));
+                stats.refutation_was_found();
+                stats.search_has_finished();
+                return;
+            }
+            Formula::True => {}
+            cnf_f => {
+                let from_conjecture = origins
+                    .iter()
+                    .any(|&id| renaming_info.get_formula_origin(id).is_conjecture());
+                for mut cl in flatten_cnf(cnf_f, &renaming_info) {
+                    cl.set_origins(origins.clone());
+                    cl.set_from_conjecture(from_conjecture);
+                    clauses.push(cl);
+                }
+            }
+        }
+    }
 
-    if cnf_f == Formula::False {
-        refutation_was_found();
-    } else if cnf_f == Formula::True {
-    } else {
-        let flattened_cnf_f = flatten_cnf(cnf_f);
-        let term_ordering = create_term_ordering(use_lpo, &flattened_cnf_f);
-        let proof_state = ProofState::new(flattened_cnf_f, term_ordering);
-        serkr_loop(proof_state, renaming_info.get_newest_variable_id());
+    if !clauses.is_empty() {
+        let var_cnt = renaming_info.get_newest_variable_id();
+        search(clauses, &renaming_info, var_cnt, input_name, options, stats);
+    }
+    stats.search_has_finished();
+}
+
+/// Selects the axioms used in the proof search according to the options.
+/// Negated conjectures are never left out, and they are used as goals along with the conjectures.
+fn select_axioms(
+    axioms: Vec<(Formula, usize)>,
+    conjectures: &[(Formula, usize)],
+    renaming_info: &RenamingInfo,
+    options: &Prover,
+    stats: &ProofStatistics,
+) -> Vec<(Formula, usize)> {
+    match options.get_axiom_selection() {
+        AxiomSelection::All => axioms,
+        AxiomSelection::Sine {
+            tolerance,
+            depth,
+            min_axioms,
+        } => {
+            let axiom_cnt = axioms
+                .iter()
+                .filter(|x| !renaming_info.get_formula_origin(x.1).is_conjecture())
+                .count();
+            if axiom_cnt < min_axioms {
+                return axioms;
+            }
+
+            let (negated_conjectures, axioms): (Vec<_>, Vec<_>) = axioms
+                .into_iter()
+                .partition(|x| renaming_info.get_formula_origin(x.1).is_conjecture());
+            let goals = conjectures
+                .iter()
+                .chain(negated_conjectures.iter())
+                .map(|x| &x.0)
+                .collect::<Vec<_>>();
+            let mut selected = sine_select(axioms, &goals, tolerance, depth);
+            if selected.len() < axiom_cnt {
+                stats.search_was_incomplete();
+            }
+            selected.extend(negated_conjectures);
+            selected.sort_by_key(|x| x.1);
+            selected
+        }
+    }
+}
+
+/// Attempts to derive a contradiction from a set of clauses with the given options.
+/// Function IDs should be positive and variable IDs negative, as usual.
+pub fn prove_clauses(clauses: Vec<Clause>, options: &Prover, stats: &ProofStatistics) {
+    stats.set_parsing_finished();
+    let var_cnt = clauses
+        .iter()
+        .flat_map(|cl| cl.iter())
+        .map(|l| {
+            l.get_lhs()
+                .min_variable_id()
+                .min(l.get_rhs().min_variable_id())
+        })
+        .min()
+        .unwrap_or(0);
+    search(
+        clauses,
+        &RenamingInfo::new(),
+        var_cnt,
+        "clauses",
+        options,
+        stats,
+    );
+    stats.search_has_finished();
+}
+
+/// Runs the proof search on a set of clauses and stores the proof or saturation found.
+fn search(
+    clauses: Vec<Clause>,
+    renaming_info: &RenamingInfo,
+    var_cnt: i64,
+    input_name: &str,
+    options: &Prover,
+    stats: &ProofStatistics,
+) {
+    // The set of support is formed by the clauses coming from the negated conjecture,
+    // so without those the restriction would prevent all inferences.
+    let set_of_support =
+        options.get_set_of_support() && clauses.iter().any(|cl| cl.is_from_conjecture());
+    if set_of_support {
+        stats.search_was_incomplete();
+    }
+    let term_ordering = create_term_ordering(options.get_use_lpo(), &clauses);
+    let arithmetic = Arithmetic::new(renaming_info);
+    let mut proof_state =
+        ProofState::new(clauses, term_ordering, arithmetic, options.get_heuristics());
+    if proof_state.get_arithmetic().is_interpreted() {
+        stats.search_was_incomplete();
+    }
+    let outcome = serkr_loop(&mut proof_state, options, stats, var_cnt, set_of_support);
+    // Numbers computed during the search need names in the output.
+    let mut renaming_info = renaming_info.clone();
+    proof_state
+        .get_arithmetic()
+        .name_new_numbers(&mut renaming_info);
+    match outcome {
+        SearchOutcome::Refutation(empty_clause) => {
+            stats.set_proof_output(refutation_to_tstp(
+                &renaming_info,
+                proof_state.get_derivations(),
+                proof_state.get_input_clauses(),
+                &empty_clause,
+                input_name,
+            ));
+            stats.set_unsat_core(refutation_to_unsat_core(
+                &renaming_info,
+                proof_state.get_derivations(),
+                &empty_clause,
+            ));
+        }
+        SearchOutcome::Saturation => {
+            stats.set_proof_output(saturation_to_tstp(&renaming_info, &proof_state.get_used()));
+        }
+        SearchOutcome::Stopped => {}
     }
-    search_has_finished();
 }
 
 #[cfg(test)]
 mod test {
     use super::prove;
     use crate::prover::proof_result::ProofResult;
-    use crate::prover::proof_statistics::get_proof_result;
-
-    // Due to the use of global variables we cannot run the tests here in parallel.
-    // Instead we have one gigantic test.
-
-    #[test]
-    fn test_problem_suite() {
-        // The normal pelletier problems, with some missing for various reasons.
-        pelletier_1();
-        pelletier_2();
-        pelletier_3();
-        pelletier_4();
-        pelletier_5();
-        pelletier_6();
-        pelletier_7();
-        pelletier_8();
-        pelletier_9();
-        pelletier_10();
-        pelletier_11();
-        pelletier_12();
-        pelletier_13();
-        pelletier_14();
-        pelletier_15_original();
-        pelletier_15_errata();
-        pelletier_16();
-        pelletier_17();
-        pelletier_18();
-        pelletier_19();
-        pelletier_20();
-        pelletier_21();
-        pelletier_22();
-        pelletier_23();
-        pelletier_24();
-        pelletier_25();
-        pelletier_26();
-        pelletier_27();
-        pelletier_28_original();
-        pelletier_28_errata();
-        pelletier_29();
-        pelletier_30();
-        pelletier_31();
-        pelletier_32();
-        pelletier_33();
-        pelletier_34_original();
-        pelletier_34_errata();
-        pelletier_35();
-        pelletier_36();
-        pelletier_37();
-        // pelletier_38();
-        pelletier_39();
-        pelletier_40_original();
-        pelletier_40_errata();
-        pelletier_41();
-        pelletier_42();
-        pelletier_43();
-        pelletier_44();
-        pelletier_45();
-        pelletier_46();
-        // pelletier_47();
-        pelletier_48();
-        pelletier_49();
-        pelletier_50();
-        pelletier_51();
-        pelletier_52();
-        // pelletier_53();
-        // pelletier_54();
-        pelletier_55();
-        pelletier_56();
-        pelletier_57();
-        pelletier_58();
-        pelletier_59();
-        pelletier_60();
-        pelletier_61();
-        pelletier_62_errata_errata();
-        pelletier_63();
-        pelletier_64();
-        pelletier_65();
-        pelletier_72();
-        pelletier_74();
-
-        // Some negated problems as well for variety.
-        pelletier_1_negated();
-        pelletier_8_negated();
-        pelletier_18_negated();
-        pelletier_22_negated();
-        pelletier_30_negated();
-        pelletier_35_negated();
-        pelletier_50_negated();
-        pelletier_56_negated();
-
-        // Other problems.
-        davis_putnam();
-        los();
-        ewd();
-        djikstra();
-        djikstra_negated();
-        group_left_inverse_means_right_inverse();
-        set_union_is_commutative();
+    use crate::prover::proof_statistics::ProofStatistics;
+    use crate::utils::hash_map::HashSet;
+    use regex::Regex;
+
+    /// Checks that every clause cited in the proof has been printed before the clause citing it.
+    fn assert_parents_printed_first(proof: &[String]) {
+        let line_regex = Regex::new(r"^cnf\(([ci]_\d+),").expect("This should always work");
+        let name_regex = Regex::new(r"\b[ci]_\d+\b").expect("This should always work");
+        let mut printed = HashSet::default();
+
+        for line in proof {
+            let name = line_regex
+                .captures(line)
+                .and_then(|caps| caps.get(1))
+                .expect("Not a clause");
+            // The symbols of the problems checked are not named like clauses.
+            for parent in name_regex.find_iter(&line[name.end()..]) {
+                assert!(
+                    printed.contains(parent.as_str()),
+                    "{} cites {} before it is printed",
+                    name.as_str(),
+                    parent.as_str()
+                );
+            }
+            assert!(printed.insert(name.as_str().to_owned()));
+        }
+    }
+
+    #[test]
+    fn proofs_cite_printed_clauses() {
+        for problem in &["p9", "p10", "p12", "p13", "p14", "p21", "p24", "p26"] {
+            let stats = ProofStatistics::new();
+            prove(&format!("test_problems/{}.p", problem), false, 32, &stats);
+            assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+
+            let proof = stats.get_proof_output();
+            assert!(proof.last().expect("Empty proof").contains("$false"));
+            assert_parents_printed_first(&proof);
+        }
     }
 
+    #[test]
     fn pelletier_1() {
-        prove("test_problems/p1.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p1.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_1_negated() {
-        prove("test_problems/p1n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p1n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_2() {
-        prove("test_problems/p2.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p2.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_3() {
-        prove("test_problems/p3.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p3.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_4() {
-        prove("test_problems/p4.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p4.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_5() {
-        prove("test_problems/p5.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p5.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_6() {
-        prove("test_problems/p6.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p6.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_7() {
-        prove("test_problems/p7.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p7.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_8() {
-        prove("test_problems/p8.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p8.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_8_negated() {
-        prove("test_problems/p8n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p8n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_9() {
-        prove("test_problems/p9.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p9.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_10() {
-        prove("test_problems/p10.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p10.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_11() {
-        prove("test_problems/p11.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p11.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_12() {
-        prove("test_problems/p12.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p12.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_13() {
-        prove("test_problems/p13.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p13.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_14() {
-        prove("test_problems/p14.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p14.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_15_original() {
-        prove("test_problems/p15o.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p15o.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_15_errata() {
-        prove("test_problems/p15e.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p15e.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_16() {
-        prove("test_problems/p16.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p16.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_17() {
-        prove("test_problems/p17.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p17.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_18() {
-        prove("test_problems/p18.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p18.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_18_negated() {
-        prove("test_problems/p18n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p18n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_19() {
-        prove("test_problems/p19.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p19.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_20() {
-        prove("test_problems/p20.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p20.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_21() {
-        prove("test_problems/p21.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p21.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_22() {
-        prove("test_problems/p22.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p22.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_22_negated() {
-        prove("test_problems/p22n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p22n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_23() {
-        prove("test_problems/p23.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p23.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_24() {
-        prove("test_problems/p24.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p24.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_25() {
-        prove("test_problems/p25.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p25.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_26() {
-        prove("test_problems/p26.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p26.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_27() {
-        prove("test_problems/p27.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p27.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_28_original() {
-        prove("test_problems/p28o.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p28o.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_28_errata() {
-        prove("test_problems/p28e.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p28e.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_29() {
-        prove("test_problems/p29.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p29.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_30() {
-        prove("test_problems/p30.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p30.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_30_negated() {
-        prove("test_problems/p30n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p30n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_31() {
-        prove("test_problems/p31.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p31.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_32() {
-        prove("test_problems/p32.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p32.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_33() {
-        prove("test_problems/p33.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p33.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_34_original() {
-        prove("test_problems/p34.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p34.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_34_errata() {
-        prove("test_problems/p34e.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p34e.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_35() {
-        prove("test_problems/p35.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p35.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_35_negated() {
-        prove("test_problems/p35n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p35n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_36() {
-        prove("test_problems/p36.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p36.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_37() {
-        prove("test_problems/p37.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p37.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_39() {
-        prove("test_problems/p39.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p39.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_40_original() {
-        prove("test_problems/p40o.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p40o.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_40_errata() {
-        prove("test_problems/p40e.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p40e.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_41() {
-        prove("test_problems/p41.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p41.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_42() {
-        prove("test_problems/p42.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p42.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_43() {
-        prove("test_problems/p43.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p43.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_44() {
-        prove("test_problems/p44.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p44.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_45() {
-        prove("test_problems/p45.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p45.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_46() {
-        prove("test_problems/p46.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p46.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_48() {
-        prove("test_problems/p48.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p48.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_49() {
-        prove("test_problems/p49.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p49.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_50() {
-        prove("test_problems/p50.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p50.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_50_negated() {
-        prove("test_problems/p50n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p50n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_51() {
-        prove("test_problems/p51.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p51.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_52() {
-        prove("test_problems/p52.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p52.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_55() {
-        prove("test_problems/p55.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p55.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_56() {
-        prove("test_problems/p56.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p56.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_56_negated() {
-        prove("test_problems/p56n.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p56n.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn pelletier_57() {
-        prove("test_problems/p57.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p57.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_58() {
-        prove("test_problems/p58.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p58.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_59() {
-        prove("test_problems/p59.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p59.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_60() {
-        prove("test_problems/p60.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p60.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_61() {
-        prove("test_problems/p61.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p61.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_62_errata_errata() {
-        prove("test_problems/p62ee.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p62ee.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_63() {
-        prove("test_problems/p63.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p63.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_64() {
-        prove("test_problems/p64.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p64.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_65() {
-        prove("test_problems/p65.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p65.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn pelletier_72() {
-        prove("test_problems/p72.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Unsatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p72.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Unsatisfiable);
     }
 
+    #[test]
     fn pelletier_74() {
-        prove("test_problems/p74.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Unsatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/p74.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Unsatisfiable);
     }
 
+    #[test]
     fn davis_putnam() {
-        prove("test_problems/davis_putnam.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/davis_putnam.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn los() {
-        prove("test_problems/los.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/los.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn ewd() {
-        prove("test_problems/ewd.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/ewd.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn djikstra() {
-        prove("test_problems/djikstra.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove("test_problems/djikstra.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn djikstra_negated() {
-        prove("test_problems/djikstra_negated.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::CounterSatisfiable);
+        let stats = ProofStatistics::new();
+        prove("test_problems/djikstra_negated.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
     }
 
+    #[test]
     fn group_left_inverse_means_right_inverse() {
+        let stats = ProofStatistics::new();
         prove(
             "test_problems/group_left_inverse_means_right_inverse.p",
             false,
             32,
+            &stats,
         );
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 
+    #[test]
     fn set_union_is_commutative() {
-        prove("test_problems/set_union_is_commutative.p", false, 32);
-        assert_eq!(get_proof_result(), ProofResult::Theorem);
+        let stats = ProofStatistics::new();
+        prove(
+            "test_problems/set_union_is_commutative.p",
+            false,
+            32,
+            &stats,
+        );
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn typed_owner() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/typed_owner.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn typed_singleton_sort() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/typed_singleton_sort.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
+    }
+
+    #[test]
+    fn arithmetic_sum() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/arithmetic_sum.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn arithmetic_instance() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/arithmetic_instance.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn arithmetic_rational() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/arithmetic_rational.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn arithmetic_false() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/arithmetic_false.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::GaveUp);
+    }
+
+    #[test]
+    fn distinct_objects() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/distinct_objects.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn distinct_objects_negated() {
+        let stats = ProofStatistics::new();
+        prove(
+            "test_problems/distinct_objects_negated.p",
+            false,
+            32,
+            &stats,
+        );
+        assert_eq!(stats.get_proof_result(), ProofResult::CounterSatisfiable);
+    }
+
+    #[test]
+    fn distinct_objects_equal_constant() {
+        let stats = ProofStatistics::new();
+        prove(
+            "test_problems/distinct_objects_equal_constant.p",
+            false,
+            32,
+            &stats,
+        );
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+
+        let stats = ProofStatistics::new();
+        prove(
+            "test_problems/distinct_objects_equal_constant.p",
+            true,
+            32,
+            &stats,
+        );
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
+    }
+
+    #[test]
+    fn distinct_predicate() {
+        let stats = ProofStatistics::new();
+        prove("test_problems/distinct_predicate.p", false, 32, &stats);
+        assert_eq!(stats.get_proof_result(), ProofResult::Theorem);
     }
 }
@@ -18,40 +18,115 @@ use crate::prover::clause_selection::clause_weight::ClauseWeight;
 use crate::prover::clause_selection::heuristic::Heuristic;
 use crate::prover::clause_selection::pick_best::{choose_heuristic, pick_best_clause};
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::feature_vector_index::FeatureVectorIndex;
+use crate::prover::data_structures::fingerprint_index::FingerprintIndex;
 use crate::prover::data_structures::pd_tree::PDTree;
+use crate::prover::data_structures::term::Term;
+use crate::prover::inference::superposition::{from_terms, into_terms};
 use crate::prover::ordering::term_ordering::TermOrdering;
-use crate::utils::hash_map::HashMap;
+use crate::prover::resource_limits::estimate_clause_memory;
+use crate::prover::simplification::arithmetic::Arithmetic;
+use crate::utils::hash_map::{HashMap, HashSet};
 use std::collections::BinaryHeap;
 
+/// Collects the function symbols of a term.
+fn collect_symbols(t: &Term, symbols: &mut HashSet<i64>) {
+    if !t.is_variable() {
+        symbols.insert(t.get_id());
+        for sub_t in t.iter() {
+            collect_symbols(sub_t, symbols);
+        }
+    }
+}
+
+/// Collects the distinct non-variable subterms of a term, leaving out truth.
+fn collect_subterms<'a>(t: &'a Term, subterms: &mut HashSet<&'a Term>) {
+    if t.is_function() && !t.is_truth() && subterms.insert(t) {
+        for sub_t in t.iter() {
+            collect_subterms(sub_t, subterms);
+        }
+    }
+}
+
+/// Get the distinct non-variable subterms of the literals of a clause.
+fn clause_subterms(cl: &Clause) -> HashSet<&Term> {
+    let mut subterms = HashSet::default();
+    for l in cl.iter() {
+        collect_subterms(l.get_lhs(), &mut subterms);
+        collect_subterms(l.get_rhs(), &mut subterms);
+    }
+    subterms
+}
+
+/// Get the symbols occurring in the clauses coming from the negated conjecture.
+fn goal_symbols(clauses: &[Clause]) -> HashSet<i64> {
+    let mut symbols = HashSet::default();
+    for cl in clauses.iter().filter(|cl| cl.is_from_conjecture()) {
+        for l in cl.iter() {
+            collect_symbols(l.get_lhs(), &mut symbols);
+            collect_symbols(l.get_rhs(), &mut symbols);
+        }
+    }
+    symbols
+}
+
 /// Contains the current proof state.
 pub struct ProofState {
-    used_clauses: Vec<Clause>,
+    used_clauses: HashMap<u64, Clause>,
     unused_clauses: HashMap<u64, Clause>,
     term_ordering: TermOrdering,
+    arithmetic: Arithmetic,
     clause_order: Vec<BinaryHeap<ClauseWeight>>,
     heuristic_order: Vec<Heuristic>,
     heuristic_use_count: Vec<usize>,
     current_heuristic_count: usize,
+    goal_symbols: HashSet<i64>,
     term_index: PDTree,
+    subterm_index: PDTree,
+    from_index: FingerprintIndex,
+    into_index: FingerprintIndex,
+    subsumption_index: FeatureVectorIndex,
     id_count: u64,
+    derivations: HashMap<u64, Clause>,
+    input_clauses: HashMap<u64, Clause>,
+    memory_estimate: usize,
 }
 
 impl ProofState {
     /// Creates a new proof state.
-    pub fn new(preprocessed_clauses: Vec<Clause>, term_order: TermOrdering) -> ProofState {
+    /// The heuristics are given with the amount of times each is used in a row.
+    pub fn new(
+        preprocessed_clauses: Vec<Clause>,
+        term_order: TermOrdering,
+        arithmetic: Arithmetic,
+        heuristics: &[(Heuristic, usize)],
+    ) -> ProofState {
         let mut state = ProofState {
-            used_clauses: Vec::new(),
+            used_clauses: HashMap::default(),
             unused_clauses: HashMap::default(),
             term_ordering: term_order,
-            clause_order: vec![BinaryHeap::new(), BinaryHeap::new()],
-            heuristic_order: vec![Heuristic::Size(2, 1), Heuristic::Age],
-            heuristic_use_count: vec![4, 1],
+            arithmetic,
+            clause_order: heuristics.iter().map(|_| BinaryHeap::new()).collect(),
+            heuristic_order: heuristics.iter().map(|&(h, _)| h).collect(),
+            heuristic_use_count: heuristics.iter().map(|&(_, n)| n).collect(),
             current_heuristic_count: 0,
+            goal_symbols: goal_symbols(&preprocessed_clauses),
             term_index: PDTree::new(),
+            subterm_index: PDTree::new(),
+            from_index: FingerprintIndex::new(),
+            into_index: FingerprintIndex::new(),
+            subsumption_index: FeatureVectorIndex::new(),
             id_count: 0,
+            derivations: HashMap::default(),
+            input_clauses: HashMap::default(),
+            memory_estimate: 0,
         };
 
-        for cl in preprocessed_clauses {
+        // Input clauses might be simplified in place, so their input versions are kept for the proofs.
+        for mut cl in preprocessed_clauses {
+            cl.set_id(state.id_count);
+            state.memory_estimate += estimate_clause_memory(&cl);
+            state.input_clauses.insert(cl.get_id(), cl.clone());
             state.add_to_unused(cl);
         }
 
@@ -71,14 +146,60 @@ impl ProofState {
     /// Picks the best clause from unused and removes it from there.
     pub fn pick_best_clause(&mut self) -> Option<Clause> {
         let i = choose_heuristic(&self.heuristic_use_count, &mut self.current_heuristic_count);
-        pick_best_clause(&mut self.unused_clauses, &mut self.clause_order[i])
+        let cl = pick_best_clause(&mut self.unused_clauses, &mut self.clause_order[i])?;
+        self.memory_estimate -= estimate_clause_memory(&cl);
+        Some(cl)
     }
 
     /// Adds the given clause to used clauses.
+    /// The clause is also saved so that proofs can be reconstructed later.
     pub fn add_to_used(&mut self, cl: Clause) {
         self.term_index
             .add_clause_to_index(&self.term_ordering, &cl);
-        self.used_clauses.push(cl);
+        for t in clause_subterms(&cl) {
+            self.subterm_index
+                .add_eq_to_index(t, &Term::new_truth(), true, true, cl.get_id());
+        }
+        for t in from_terms(&self.term_ordering, &cl) {
+            self.from_index.add_term(t, cl.get_id());
+        }
+        for t in into_terms(&self.term_ordering, &cl) {
+            self.into_index.add_term(t, cl.get_id());
+        }
+        // Unit clauses are handled by the term index instead.
+        if !cl.is_unit() {
+            self.subsumption_index.add_clause(&cl);
+        }
+        // The clause is stored twice: once in used clauses and once in derivations.
+        self.memory_estimate += 2 * estimate_clause_memory(&cl);
+        self.derivations.insert(cl.get_id(), cl.clone());
+        self.used_clauses.insert(cl.get_id(), cl);
+    }
+
+    /// Removes the used clauses with the given IDs, also from the indices.
+    pub fn remove_from_used(&mut self, ids: &HashSet<u64>) {
+        for id in ids {
+            let cl = match self.used_clauses.remove(id) {
+                Some(cl) => cl,
+                None => continue,
+            };
+            self.term_index
+                .remove_clause_from_index(&self.term_ordering, &cl);
+            for t in clause_subterms(&cl) {
+                self.subterm_index.remove_eq_from_index(t, cl.get_id());
+            }
+            for t in from_terms(&self.term_ordering, &cl) {
+                self.from_index.remove_term(t, cl.get_id());
+            }
+            for t in into_terms(&self.term_ordering, &cl) {
+                self.into_index.remove_term(t, cl.get_id());
+            }
+            if !cl.is_unit() {
+                self.subsumption_index.remove_clause(&cl);
+            }
+            // The copy in derivations is still needed for reconstructing proofs.
+            self.memory_estimate -= estimate_clause_memory(&cl);
+        }
     }
 
     /// Adds the given clause to unused clauses.
@@ -86,8 +207,9 @@ impl ProofState {
         // Give a unique ID to the clause.
         cl.set_id(self.id_count);
         self.id_count += 1;
+        self.memory_estimate += estimate_clause_memory(&cl);
         for i in 0..self.heuristic_order.len() {
-            let cw = self.heuristic_order[i].new_clauseweight(&cl);
+            let cw = self.heuristic_order[i].new_clauseweight(&cl, &self.goal_symbols);
             self.clause_order[i].push(cw);
         }
         self.unused_clauses.insert(cl.get_id(), cl);
@@ -98,13 +220,86 @@ impl ProofState {
         &self.term_ordering
     }
 
+    /// Get a reference to the interpretation of arithmetic.
+    pub fn get_arithmetic(&self) -> &Arithmetic {
+        &self.arithmetic
+    }
+
+    /// Get a mutable reference to the interpretation of arithmetic.
+    pub fn get_arithmetic_mut(&mut self) -> &mut Arithmetic {
+        &mut self.arithmetic
+    }
+
     /// Get a reference to the term index.
     pub fn get_term_index(&self) -> &PDTree {
         &self.term_index
     }
 
-    /// Get a reference to the used clauses.
-    pub fn get_used(&self) -> &Vec<Clause> {
-        &self.used_clauses
+    /// Get the used clauses in the order they were created.
+    pub fn get_used(&self) -> Vec<&Clause> {
+        let mut used = self.used_clauses.values().collect::<Vec<_>>();
+        used.sort_by_key(|cl| cl.get_id());
+        used
+    }
+
+    /// Get the used clauses with the given IDs, in the order they were created.
+    fn get_used_with_ids(&self, ids: HashSet<u64>) -> impl Iterator<Item = &Clause> {
+        let mut ids = ids.into_iter().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.into_iter().map(move |id| &self.used_clauses[&id])
+    }
+
+    /// Get the used clauses which might be able to take part in a superposition inference with the given clause.
+    /// The other used clauses cannot have any term unifiable with a suitable term of the given clause.
+    pub fn get_superposition_partners(&self, cl: &Clause) -> impl Iterator<Item = &Clause> {
+        let mut ids = HashSet::default();
+        for t in from_terms(&self.term_ordering, cl) {
+            self.into_index.unifiable_ids(t, &mut ids);
+        }
+        for t in into_terms(&self.term_ordering, cl) {
+            self.from_index.unifiable_ids(t, &mut ids);
+        }
+        self.get_used_with_ids(ids)
+    }
+
+    /// Get the used clauses which contain an instance of one of the given non-variable terms as a subterm.
+    pub fn get_clauses_with_instances(&self, terms: &[&Term]) -> impl Iterator<Item = &Clause> {
+        let mut ids = HashSet::default();
+        for t in terms {
+            assert!(!t.is_variable());
+            for (_, _, _, _, id) in self.subterm_index.iter_specializations(t, true) {
+                ids.insert(id);
+            }
+        }
+        self.get_used_with_ids(ids)
+    }
+
+    /// Get the non-unit used clauses which might subsume the given clause.
+    pub fn get_possible_subsumers(&self, cl: &Clause) -> impl Iterator<Item = &Clause> {
+        let mut ids = HashSet::default();
+        self.subsumption_index.possible_subsumers(cl, &mut ids);
+        self.get_used_with_ids(ids)
+    }
+
+    /// Get the non-unit used clauses which the given clause might subsume.
+    pub fn get_possibly_subsumed(&self, cl: &Clause) -> impl Iterator<Item = &Clause> {
+        let mut ids = HashSet::default();
+        self.subsumption_index.possibly_subsumed(cl, &mut ids);
+        self.get_used_with_ids(ids)
+    }
+
+    /// Get a rough estimate of the memory used by the stored clauses, in bytes.
+    pub fn get_memory_estimate(&self) -> usize {
+        self.memory_estimate
+    }
+
+    /// Get a reference to every clause which has ever been used, indexed by ID.
+    pub fn get_derivations(&self) -> &HashMap<u64, Clause> {
+        &self.derivations
+    }
+
+    /// Get a reference to the input clauses as they were before any simplifications, indexed by ID.
+    pub fn get_input_clauses(&self) -> &HashMap<u64, Clause> {
+        &self.input_clauses
     }
 }
@@ -14,204 +14,299 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
-// This entire file is a dirty fucking hack.
-// We now have global state, so we can NEVER have several proof searches running concurrently.
-// Notably, proof search tests have to be clustered.
-// I haven't figured out a better way to get the statistics out in case the search thread gets stuck.
-
 use crate::prover::proof_result::ProofResult;
+use crate::tptp_parser::parse_error::TptpParseError;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-
-static INITIAL_CLAUSES: AtomicUsize = AtomicUsize::new(0);
-static ITERATIONS: AtomicUsize = AtomicUsize::new(0);
-static TRIVIAL_COUNT: AtomicUsize = AtomicUsize::new(0);
-static FS_COUNT: AtomicUsize = AtomicUsize::new(0);
-static BS_COUNT: AtomicUsize = AtomicUsize::new(0);
-static SP_COUNT: AtomicUsize = AtomicUsize::new(0);
-static EF_COUNT: AtomicUsize = AtomicUsize::new(0);
-static ER_COUNT: AtomicUsize = AtomicUsize::new(0);
-static TRIVIAL_INFERENCE_COUNT: AtomicUsize = AtomicUsize::new(0);
-
-static CONTAINS_CONJECTURES: AtomicBool = AtomicBool::new(false);
-static PARSING_FINISHED: AtomicBool = AtomicBool::new(false);
-static SEARCH_FINISHED: AtomicBool = AtomicBool::new(false);
-static REFUTATION_FOUND: AtomicBool = AtomicBool::new(false);
-
-/// Resets all the statistics.
-pub fn reset_statistics() {
-    set_initial_clauses(0);
-    ITERATIONS.store(0, Ordering::SeqCst);
-    TRIVIAL_COUNT.store(0, Ordering::SeqCst);
-    FS_COUNT.store(0, Ordering::SeqCst);
-    BS_COUNT.store(0, Ordering::SeqCst);
-    SP_COUNT.store(0, Ordering::SeqCst);
-    EF_COUNT.store(0, Ordering::SeqCst);
-    ER_COUNT.store(0, Ordering::SeqCst);
-    TRIVIAL_INFERENCE_COUNT.store(0, Ordering::SeqCst);
-
-    CONTAINS_CONJECTURES.store(false, Ordering::SeqCst);
-    PARSING_FINISHED.store(false, Ordering::SeqCst);
-    SEARCH_FINISHED.store(false, Ordering::SeqCst);
-    REFUTATION_FOUND.store(false, Ordering::SeqCst);
+use std::sync::Mutex;
+
+/// Statistics and status flags of a single proof search.
+/// Everything is stored in atomics so that the search can be monitored from another thread,
+/// e.g. by sharing the statistics through an `Arc`.
+#[derive(Debug, Default)]
+pub struct ProofStatistics {
+    initial_clauses: AtomicUsize,
+    iterations: AtomicUsize,
+    trivial_count: AtomicUsize,
+    fs_count: AtomicUsize,
+    bs_count: AtomicUsize,
+    br_count: AtomicUsize,
+    sp_count: AtomicUsize,
+    ef_count: AtomicUsize,
+    er_count: AtomicUsize,
+    trivial_inference_count: AtomicUsize,
+
+    contains_conjectures: AtomicBool,
+    parsing_finished: AtomicBool,
+    search_finished: AtomicBool,
+    refutation_found: AtomicBool,
+    cancelled: AtomicBool,
+    resources_exhausted: AtomicBool,
+    incomplete: AtomicBool,
+
+    proof_output: Mutex<Vec<String>>,
+    unsat_core: Mutex<Vec<String>>,
+    parse_error: Mutex<Option<TptpParseError>>,
 }
 
-/// Set the amount of initial clauses.
-pub fn set_initial_clauses(x: usize) {
-    INITIAL_CLAUSES.store(x, Ordering::SeqCst);
-}
+impl ProofStatistics {
+    /// Creates a new set of statistics for a proof search.
+    pub fn new() -> ProofStatistics {
+        ProofStatistics::default()
+    }
 
-/// Get the amount of initial clauses.
-pub fn get_initial_clauses() -> usize {
-    INITIAL_CLAUSES.load(Ordering::SeqCst)
-}
+    /// Set the amount of initial clauses.
+    pub fn set_initial_clauses(&self, x: usize) {
+        self.initial_clauses.store(x, Ordering::SeqCst);
+    }
 
-/// Increment the iteration count.
-pub fn increment_iteration_count() {
-    ITERATIONS.fetch_add(1, Ordering::SeqCst);
-}
+    /// Get the amount of initial clauses.
+    pub fn get_initial_clauses(&self) -> usize {
+        self.initial_clauses.load(Ordering::SeqCst)
+    }
 
-/// Get the iteration count.
-pub fn get_iteration_count() -> usize {
-    ITERATIONS.load(Ordering::SeqCst)
-}
+    /// Increment the iteration count.
+    pub fn increment_iteration_count(&self) {
+        self.iterations.fetch_add(1, Ordering::SeqCst);
+    }
 
-/// Increment the amount of trivial clauses discovered during the proof search.
-pub fn increment_trivial_count() {
-    TRIVIAL_COUNT.fetch_add(1, Ordering::SeqCst);
-}
+    /// Get the iteration count.
+    pub fn get_iteration_count(&self) -> usize {
+        self.iterations.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of trivial clauses discovered during the proof search.
-pub fn get_trivial_count() -> usize {
-    TRIVIAL_COUNT.load(Ordering::SeqCst)
-}
+    /// Increment the amount of trivial clauses discovered during the proof search.
+    pub fn increment_trivial_count(&self) {
+        self.trivial_count.fetch_add(1, Ordering::SeqCst);
+    }
 
-/// Increment the amount of forward subsumed clauses during the proof search.
-pub fn increment_forward_subsumed_count() {
-    FS_COUNT.fetch_add(1, Ordering::SeqCst);
-}
+    /// Get the amount of trivial clauses discovered during the proof search.
+    pub fn get_trivial_count(&self) -> usize {
+        self.trivial_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of forward subsumed clauses during the proof search.
-pub fn get_forward_subsumed_count() -> usize {
-    FS_COUNT.load(Ordering::SeqCst)
-}
+    /// Increment the amount of forward subsumed clauses during the proof search.
+    pub fn increment_forward_subsumed_count(&self) {
+        self.fs_count.fetch_add(1, Ordering::SeqCst);
+    }
 
-/// Add to the amount of backward subsumed clauses during the proof search.
-pub fn add_backward_subsumed_count(x: usize) {
-    BS_COUNT.fetch_add(x, Ordering::SeqCst);
-}
+    /// Get the amount of forward subsumed clauses during the proof search.
+    pub fn get_forward_subsumed_count(&self) -> usize {
+        self.fs_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of backward subsumed clauses during the proof search.
-pub fn get_backward_subsumed_count() -> usize {
-    BS_COUNT.load(Ordering::SeqCst)
-}
+    /// Add to the amount of backward subsumed clauses during the proof search.
+    pub fn add_backward_subsumed_count(&self, x: usize) {
+        self.bs_count.fetch_add(x, Ordering::SeqCst);
+    }
 
-/// Add to the amount of clauses inferred by positive and negative superposition.
-pub fn add_superposition_inferred_count(x: usize) {
-    SP_COUNT.fetch_add(x, Ordering::SeqCst);
-}
+    /// Get the amount of backward subsumed clauses during the proof search.
+    pub fn get_backward_subsumed_count(&self) -> usize {
+        self.bs_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of clauses inferred by positive and negative superposition.
-pub fn get_superposition_inferred_count() -> usize {
-    SP_COUNT.load(Ordering::SeqCst)
-}
+    /// Add to the amount of clauses backward rewritten during the proof search.
+    pub fn add_backward_rewritten_count(&self, x: usize) {
+        self.br_count.fetch_add(x, Ordering::SeqCst);
+    }
 
-/// Add to the amount of clauses inferred by equality factoring.
-pub fn add_equality_factoring_inferred_count(x: usize) {
-    EF_COUNT.fetch_add(x, Ordering::SeqCst);
-}
+    /// Get the amount of clauses backward rewritten during the proof search.
+    pub fn get_backward_rewritten_count(&self) -> usize {
+        self.br_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of clauses inferred by equality factoring.
-pub fn get_equality_factoring_inferred_count() -> usize {
-    EF_COUNT.load(Ordering::SeqCst)
-}
+    /// Add to the amount of clauses inferred by positive and negative superposition.
+    pub fn add_superposition_inferred_count(&self, x: usize) {
+        self.sp_count.fetch_add(x, Ordering::SeqCst);
+    }
 
-/// Add to the amount of clauses inferred by equality resolution.
-pub fn add_equality_resolution_inferred_count(x: usize) {
-    ER_COUNT.fetch_add(x, Ordering::SeqCst);
-}
+    /// Get the amount of clauses inferred by positive and negative superposition.
+    pub fn get_superposition_inferred_count(&self) -> usize {
+        self.sp_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of clauses inferred by equality resolution.
-pub fn get_equality_resolution_inferred_count() -> usize {
-    ER_COUNT.load(Ordering::SeqCst)
-}
+    /// Add to the amount of clauses inferred by equality factoring.
+    pub fn add_equality_factoring_inferred_count(&self, x: usize) {
+        self.ef_count.fetch_add(x, Ordering::SeqCst);
+    }
 
-/// Increment the amount of trivial inferences during the proof search.
-pub fn increment_trivial_inference_count() {
-    TRIVIAL_INFERENCE_COUNT.fetch_add(1, Ordering::SeqCst);
-}
+    /// Get the amount of clauses inferred by equality factoring.
+    pub fn get_equality_factoring_inferred_count(&self) -> usize {
+        self.ef_count.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of trivial inferences during the proof search.
-pub fn get_trivial_inference_count() -> usize {
-    TRIVIAL_INFERENCE_COUNT.load(Ordering::SeqCst)
-}
+    /// Add to the amount of clauses inferred by equality resolution.
+    pub fn add_equality_resolution_inferred_count(&self, x: usize) {
+        self.er_count.fetch_add(x, Ordering::SeqCst);
+    }
 
-/// Set whether the problem contains conjectures or not.
-pub fn set_contains_conjectures(x: bool) {
-    CONTAINS_CONJECTURES.store(x, Ordering::SeqCst);
-}
+    /// Get the amount of clauses inferred by equality resolution.
+    pub fn get_equality_resolution_inferred_count(&self) -> usize {
+        self.er_count.load(Ordering::SeqCst)
+    }
 
-/// Check if the problem to analyze contains conjectures.
-fn contains_conjectures() -> bool {
-    CONTAINS_CONJECTURES.load(Ordering::SeqCst)
-}
+    /// Increment the amount of trivial inferences during the proof search.
+    pub fn increment_trivial_inference_count(&self) {
+        self.trivial_inference_count.fetch_add(1, Ordering::SeqCst);
+    }
 
-/// Set a flag for finishing parsing.
-pub fn set_parsing_finished() {
-    PARSING_FINISHED.store(true, Ordering::SeqCst);
-}
+    /// Get the amount of trivial inferences during the proof search.
+    pub fn get_trivial_inference_count(&self) -> usize {
+        self.trivial_inference_count.load(Ordering::SeqCst)
+    }
 
-/// Check if parsing was finished
-pub fn has_parsing_finished() -> bool {
-    PARSING_FINISHED.load(Ordering::SeqCst)
-}
+    /// Set whether the problem contains conjectures or not.
+    pub fn set_contains_conjectures(&self, x: bool) {
+        self.contains_conjectures.store(x, Ordering::SeqCst);
+    }
 
-/// Sets a flag for a refutation found during the proof search.
-pub fn refutation_was_found() {
-    REFUTATION_FOUND.store(true, Ordering::SeqCst)
-}
+    /// Check if the problem to analyze contains conjectures.
+    fn contains_conjectures(&self) -> bool {
+        self.contains_conjectures.load(Ordering::SeqCst)
+    }
 
-/// Checks if a refutation was found.
-pub fn was_refutation_found() -> bool {
-    REFUTATION_FOUND.load(Ordering::SeqCst)
-}
+    /// Set a flag for finishing parsing.
+    pub fn set_parsing_finished(&self) {
+        self.parsing_finished.store(true, Ordering::SeqCst);
+    }
 
-/// Sets a flag for a finished search.
-pub fn search_has_finished() {
-    SEARCH_FINISHED.store(true, Ordering::SeqCst)
-}
+    /// Check if parsing was finished
+    pub fn has_parsing_finished(&self) -> bool {
+        self.parsing_finished.load(Ordering::SeqCst)
+    }
 
-/// Checks if the proof search has finished.
-pub fn has_search_finished() -> bool {
-    SEARCH_FINISHED.load(Ordering::SeqCst)
-}
+    /// Sets a flag for a refutation found during the proof search.
+    pub fn refutation_was_found(&self) {
+        self.refutation_found.store(true, Ordering::SeqCst)
+    }
 
-/// Get the amount of nonredundant analyzed clauses.
-pub fn get_nonredundant_analyzed_count() -> usize {
-    get_iteration_count() - get_trivial_count() - get_forward_subsumed_count()
-}
+    /// Checks if a refutation was found.
+    pub fn was_refutation_found(&self) -> bool {
+        self.refutation_found.load(Ordering::SeqCst)
+    }
 
-/// Get the amount of inferred clauses.
-pub fn get_inferred_count() -> usize {
-    get_superposition_inferred_count()
-        + get_equality_factoring_inferred_count()
-        + get_equality_resolution_inferred_count()
-}
+    /// Sets a flag for a finished search.
+    pub fn search_has_finished(&self) {
+        self.search_finished.store(true, Ordering::SeqCst)
+    }
 
-/// Get the amount of nontrivial inferred clauses.
-pub fn get_nontrivial_inferred_count() -> usize {
-    get_inferred_count() - get_trivial_inference_count()
-}
+    /// Checks if the proof search has finished.
+    pub fn has_search_finished(&self) -> bool {
+        self.search_finished.load(Ordering::SeqCst)
+    }
+
+    /// Sets a flag for a search which was cancelled before it could finish.
+    pub fn search_was_cancelled(&self) {
+        self.cancelled.store(true, Ordering::SeqCst)
+    }
+
+    /// Checks if the search was cancelled.
+    pub fn was_search_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Sets a flag for a search which was stopped due to exceeding a resource limit.
+    pub fn resources_were_exhausted(&self) {
+        self.resources_exhausted.store(true, Ordering::SeqCst)
+    }
+
+    /// Checks if the search was stopped due to exceeding a resource limit.
+    pub fn were_resources_exhausted(&self) -> bool {
+        self.resources_exhausted.load(Ordering::SeqCst)
+    }
+
+    /// Sets a flag for a search which cannot conclude anything from saturating the clauses.
+    /// This happens when the problem uses theories which the prover only partially understands,
+    /// or when some of the axioms were left out.
+    pub fn search_was_incomplete(&self) {
+        self.incomplete.store(true, Ordering::SeqCst)
+    }
+
+    /// Checks if saturating the clauses is inconclusive.
+    pub fn was_search_incomplete(&self) -> bool {
+        self.incomplete.load(Ordering::SeqCst)
+    }
+
+    /// Stores the error which stopped the problem from being parsed.
+    pub fn set_parse_error(&self, e: TptpParseError) {
+        *self.parse_error.lock().expect("Parse error lock poisoned") = Some(e);
+    }
+
+    /// Get the error which stopped the problem from being parsed, if any.
+    pub fn get_parse_error(&self) -> Option<TptpParseError> {
+        self.parse_error
+            .lock()
+            .expect("Parse error lock poisoned")
+            .clone()
+    }
+
+    /// Stores the proof or the saturated clause set found, in TSTP format.
+    pub fn set_proof_output(&self, lines: Vec<String>) {
+        *self
+            .proof_output
+            .lock()
+            .expect("Proof output lock poisoned") = lines;
+    }
+
+    /// Get the proof or the saturated clause set found in TSTP format.
+    pub fn get_proof_output(&self) -> Vec<String> {
+        self.proof_output
+            .lock()
+            .expect("Proof output lock poisoned")
+            .clone()
+    }
+
+    /// Stores the names of the input formulas used in the refutation found.
+    pub fn set_unsat_core(&self, names: Vec<String>) {
+        *self.unsat_core.lock().expect("Unsat core lock poisoned") = names;
+    }
+
+    /// Get the names of the input formulas used in the refutation found.
+    pub fn get_unsat_core(&self) -> Vec<String> {
+        self.unsat_core
+            .lock()
+            .expect("Unsat core lock poisoned")
+            .clone()
+    }
+
+    /// Get the amount of nonredundant analyzed clauses.
+    pub fn get_nonredundant_analyzed_count(&self) -> usize {
+        self.get_iteration_count() - self.get_trivial_count() - self.get_forward_subsumed_count()
+    }
+
+    /// Get the amount of inferred clauses.
+    pub fn get_inferred_count(&self) -> usize {
+        self.get_superposition_inferred_count()
+            + self.get_equality_factoring_inferred_count()
+            + self.get_equality_resolution_inferred_count()
+    }
+
+    /// Get the amount of nontrivial inferred clauses.
+    pub fn get_nontrivial_inferred_count(&self) -> usize {
+        self.get_inferred_count() - self.get_trivial_inference_count()
+    }
 
-/// Get the proof result.
-pub fn get_proof_result() -> ProofResult {
-    if !has_parsing_finished() && has_search_finished() {
-        ProofResult::Error("Parsing error".to_owned())
-    } else if !has_parsing_finished() || !has_search_finished() {
-        ProofResult::Timeout
-    } else if was_refutation_found() {
-        ProofResult::new_refutation(contains_conjectures())
-    } else {
-        ProofResult::new_saturation(contains_conjectures())
+    /// Get the proof result.
+    pub fn get_proof_result(&self) -> ProofResult {
+        if !self.has_parsing_finished() && self.has_search_finished() {
+            match self.get_parse_error() {
+                Some(TptpParseError::Syntax(e)) => ProofResult::SyntaxError(e.to_string()),
+                Some(TptpParseError::Input(s)) => ProofResult::InputError(s),
+                None => ProofResult::Error("Parsing error".to_owned()),
+            }
+        } else if !self.has_parsing_finished()
+            || !self.has_search_finished()
+            || self.was_search_cancelled()
+        {
+            ProofResult::Timeout
+        } else if self.were_resources_exhausted() {
+            ProofResult::ResourceOut
+        } else if self.was_refutation_found() {
+            ProofResult::new_refutation(self.contains_conjectures())
+        } else if self.was_search_incomplete() {
+            ProofResult::GaveUp
+        } else {
+            ProofResult::new_saturation(self.contains_conjectures())
+        }
     }
 }
 
@@ -0,0 +1,159 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::literal::Literal;
+use crate::prover::data_structures::term_bank::TermEntry;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A token for stopping a proof search from another thread.
+/// Clones of a token share the same state.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    flags: Vec<Arc<AtomicBool>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token which has not been cancelled.
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            flags: vec![Arc::new(AtomicBool::new(false))],
+        }
+    }
+
+    /// Creates a new token which is cancelled when either it or the current token is cancelled.
+    /// Cancelling the new token does not cancel the current one.
+    pub fn child(&self) -> CancellationToken {
+        let mut flags = self.flags.clone();
+        flags.push(Arc::new(AtomicBool::new(false)));
+        CancellationToken { flags }
+    }
+
+    /// Cancels the token and all the tokens created from it.
+    pub fn cancel(&self) {
+        if let Some(flag) = self.flags.last() {
+            flag.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Checks if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.flags.iter().any(|flag| flag.load(Ordering::SeqCst))
+    }
+}
+
+/// Limits on the resources a single proof search may use.
+/// A limit of `None` means that the resource is unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceLimits {
+    max_iterations: Option<usize>,
+    max_generated_clauses: Option<usize>,
+    max_memory_bytes: Option<usize>,
+}
+
+impl ResourceLimits {
+    /// Creates a new set of limits with every resource unlimited.
+    pub fn new() -> ResourceLimits {
+        ResourceLimits::default()
+    }
+
+    /// Set the maximum amount of given clauses to analyze.
+    pub fn set_max_iterations(&mut self, x: usize) {
+        self.max_iterations = Some(x);
+    }
+
+    /// Set the maximum amount of clauses to generate by inferences.
+    pub fn set_max_generated_clauses(&mut self, x: usize) {
+        self.max_generated_clauses = Some(x);
+    }
+
+    /// Set the maximum amount of memory used for storing clauses, in bytes.
+    pub fn set_max_memory_bytes(&mut self, x: usize) {
+        self.max_memory_bytes = Some(x);
+    }
+
+    /// Checks if the search should be stopped.
+    /// That is the case if all the iterations are used up or too many clauses or too much memory were used.
+    pub fn exhausted(
+        &self,
+        iterations: usize,
+        generated_clauses: usize,
+        memory_bytes: usize,
+    ) -> bool {
+        self.max_iterations.is_some_and(|max| iterations >= max)
+            || self
+                .max_generated_clauses
+                .is_some_and(|max| generated_clauses > max)
+            || self.max_memory_bytes.is_some_and(|max| memory_bytes > max)
+    }
+}
+
+/// Estimates the amount of memory used by a clause, in bytes.
+/// The estimate is rough, but it is cheap to compute and grows with the size of the clause.
+/// Subterms shared with other clauses are counted too, so the estimate errs on the high side.
+pub fn estimate_clause_memory(cl: &Clause) -> usize {
+    size_of::<Clause>()
+        + cl.size() * size_of::<Literal>()
+        + cl.symbol_count(1, 1) as usize * size_of::<TermEntry>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CancellationToken, ResourceLimits};
+
+    #[test]
+    fn cancellation_token_1() {
+        let token = CancellationToken::new();
+        let child = token.child();
+        assert!(!token.is_cancelled());
+        assert!(!child.is_cancelled());
+
+        child.cancel();
+        assert!(!token.is_cancelled());
+        assert!(child.is_cancelled());
+    }
+
+    #[test]
+    fn cancellation_token_2() {
+        let token = CancellationToken::new();
+        let child = token.child();
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        assert!(child.is_cancelled());
+    }
+
+    #[test]
+    fn resource_limits_1() {
+        let mut limits = ResourceLimits::new();
+        assert!(!limits.exhausted(1000, 1000, 1000));
+
+        limits.set_max_iterations(10);
+        assert!(!limits.exhausted(9, 1000, 1000));
+        assert!(limits.exhausted(10, 1000, 1000));
+
+        limits.set_max_memory_bytes(500);
+        assert!(!limits.exhausted(0, 0, 500));
+        assert!(limits.exhausted(0, 0, 501));
+    }
+}
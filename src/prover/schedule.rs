@@ -0,0 +1,362 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::axiom_selection::AxiomSelection;
+use crate::prover::builder::{Prover, ProverOutput};
+use crate::prover::clause_selection::heuristic::{parse_heuristics, Heuristic};
+use crate::prover::inference::literal_selection::LiteralSelection;
+use crate::utils::stopwatch::Stopwatch;
+use std::fs::File;
+use std::io::Read;
+
+/// The schedule used when no other schedule is given.
+/// Problems with few axioms use all of them even when SInE is on.
+const DEFAULT_SCHEDULE: &str = "
+# name      options
+kbo         time=4 sine=on
+lpo         time=2 ordering=lpo sine=on
+kbo-age     time=2 heuristics=size(2,1):1,age:1
+kbo-size    time=1 heuristics=size(1,1):9,age:1 renaming=0 sine=on sine-tolerance=3
+";
+
+/// One configuration of the prover in a schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strategy {
+    name: String,
+    time_share: u64,
+    use_lpo: bool,
+    renaming_limit: u64,
+    heuristics: Vec<(Heuristic, usize)>,
+    axiom_selection: AxiomSelection,
+    set_of_support: bool,
+    literal_selection: LiteralSelection,
+}
+
+impl Strategy {
+    /// Creates a strategy with the default options of the prover and a time share of 1.
+    pub fn new(name: &str) -> Strategy {
+        let defaults = Prover::new();
+        Strategy {
+            name: name.to_owned(),
+            time_share: 1,
+            use_lpo: defaults.get_use_lpo(),
+            renaming_limit: defaults.get_renaming_limit(),
+            heuristics: defaults.get_heuristics().to_vec(),
+            axiom_selection: defaults.get_axiom_selection(),
+            set_of_support: defaults.get_set_of_support(),
+            literal_selection: defaults.get_literal_selection(),
+        }
+    }
+
+    /// Parses a strategy from a name followed by options of the form `key=value`.
+    fn parse(line: &str) -> Result<Strategy, String> {
+        let mut tokens = line.split_whitespace();
+        let mut strategy = Strategy::new(tokens.next().expect("The line should not be empty"));
+
+        for option in tokens {
+            let (key, value) = option.split_once('=').ok_or_else(|| {
+                format!("Expected an option of the form key=value, got {}", option)
+            })?;
+            strategy.set_option(key, value)?;
+        }
+
+        Ok(strategy)
+    }
+
+    /// Sets the option with the given key to the value given.
+    fn set_option(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let (mut tolerance, mut depth, mut min_axioms) = match self.axiom_selection {
+            AxiomSelection::Sine {
+                tolerance,
+                depth,
+                min_axioms,
+            } => (tolerance, depth, min_axioms),
+            AxiomSelection::All => (1.5, None, 64),
+        };
+        let invalid = || format!("Invalid value {} for {}", value, key);
+
+        match key {
+            "time" => {
+                self.time_share = value.parse().map_err(|_| invalid())?;
+                return Ok(());
+            }
+            "ordering" => {
+                self.use_lpo = match value {
+                    "kbo" => false,
+                    "lpo" => true,
+                    _ => return Err(invalid()),
+                };
+                return Ok(());
+            }
+            "renaming" => {
+                self.renaming_limit = value.parse().map_err(|_| invalid())?;
+                return Ok(());
+            }
+            "heuristics" => {
+                self.heuristics = parse_heuristics(value)?;
+                return Ok(());
+            }
+            "selection" => {
+                self.literal_selection = value.parse()?;
+                return Ok(());
+            }
+            "sos" => {
+                self.set_of_support = match value {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(invalid()),
+                };
+                return Ok(());
+            }
+            "sine" => match value {
+                "on" => {}
+                "off" => {
+                    self.axiom_selection = AxiomSelection::All;
+                    return Ok(());
+                }
+                _ => return Err(invalid()),
+            },
+            "sine-tolerance" => tolerance = value.parse().map_err(|_| invalid())?,
+            "sine-depth" => depth = Some(value.parse().map_err(|_| invalid())?),
+            "sine-min-axioms" => min_axioms = value.parse().map_err(|_| invalid())?,
+            _ => return Err(format!("Unknown option {}", key)),
+        }
+
+        // All the SInE options turn SInE on.
+        self.axiom_selection = AxiomSelection::Sine {
+            tolerance,
+            depth,
+            min_axioms,
+        };
+        Ok(())
+    }
+
+    /// Applies the options of the strategy to a prover.
+    /// Options which are not part of a strategy, like the resource limits, are kept.
+    pub fn apply(&self, prover: Prover) -> Prover {
+        prover
+            .use_lpo(self.use_lpo)
+            .renaming_limit(self.renaming_limit)
+            .heuristics(self.heuristics.clone())
+            .expect("The heuristics of a strategy are always validated")
+            .axiom_selection(self.axiom_selection)
+            .set_of_support(self.set_of_support)
+            .literal_selection(self.literal_selection)
+    }
+
+    /// Get the name of the strategy.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the share of the time limit given to the strategy, relative to the other strategies.
+    pub fn get_time_share(&self) -> u64 {
+        self.time_share
+    }
+}
+
+/// A list of strategies which are tried one after another until one of them gives a definitive result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    strategies: Vec<Strategy>,
+}
+
+impl Default for Schedule {
+    fn default() -> Schedule {
+        Schedule::parse(DEFAULT_SCHEDULE).expect("The default schedule should be valid")
+    }
+}
+
+impl Schedule {
+    /// Creates a schedule from a list of strategies.
+    pub fn new(strategies: Vec<Strategy>) -> Schedule {
+        assert!(
+            !strategies.is_empty(),
+            "A schedule needs at least one strategy"
+        );
+        Schedule { strategies }
+    }
+
+    /// Parses a schedule with one strategy per line.
+    /// Each line has the name of the strategy followed by its options, for example
+    /// `lpo-age time=2 ordering=lpo heuristics=size(2,1):1,age:1 sine=off`.
+    /// The options not given are the defaults of the prover. Lines starting with `#` are comments.
+    pub fn parse(s: &str) -> Result<Schedule, String> {
+        let mut strategies = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                strategies
+                    .push(Strategy::parse(line).map_err(|e| format!("Line {}: {}", i + 1, e))?);
+            }
+        }
+
+        if strategies.is_empty() {
+            Err("The schedule doesn't contain any strategies".to_owned())
+        } else {
+            Ok(Schedule::new(strategies))
+        }
+    }
+
+    /// Reads and parses a schedule from a file.
+    pub fn from_file(path: &str) -> Result<Schedule, String> {
+        let mut s = String::new();
+        File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut s))
+            .map_err(|e| format!("Could not read the schedule {}: {}", path, e))?;
+        Schedule::parse(&s)
+    }
+
+    /// Get the strategies of the schedule.
+    pub fn get_strategies(&self) -> &[Strategy] {
+        &self.strategies
+    }
+
+    /// Attempts to prove the problem in the TPTP file at the location given.
+    /// Also returns the strategy which produced the result.
+    pub fn prove_file(&self, prover: &Prover, path: &str) -> (ProverOutput, &Strategy) {
+        self.run(prover, |p| p.prove_file(path))
+    }
+
+    /// Attempts to prove a problem in TPTP format given as a string.
+    /// Also returns the strategy which produced the result.
+    pub fn prove_str(&self, prover: &Prover, problem: &str) -> (ProverOutput, &Strategy) {
+        self.run(prover, |p| p.prove_str(problem))
+    }
+
+    /// Runs the strategies one after another until one of them gives a definitive result.
+    /// The time limit of the prover is split between the strategies by their time shares,
+    /// and the time left over by strategies which finish early goes to the later ones.
+    /// Without a time limit the later strategies only run if the earlier ones give up.
+    fn run<F>(&self, prover: &Prover, prove: F) -> (ProverOutput, &Strategy)
+    where
+        F: Fn(&Prover) -> ProverOutput,
+    {
+        let mut sw = Stopwatch::new();
+        sw.start();
+        let mut shares_left = self.strategies.iter().map(|s| s.time_share).sum::<u64>();
+        let mut i = 0;
+
+        loop {
+            let strategy = &self.strategies[i];
+            let mut options = strategy.apply(prover.clone());
+            if let Some(time_limit_ms) = prover.get_time_limit_ms() {
+                let time_left_ms = time_limit_ms.saturating_sub(sw.elapsed_ms());
+                let slice_ms = (time_left_ms * strategy.time_share)
+                    .checked_div(shares_left)
+                    .unwrap_or(time_left_ms);
+                options = options.time_limit_ms(slice_ms);
+            }
+            shares_left -= strategy.time_share;
+
+            let output = prove(&options);
+            let result = output.get_result();
+            i += 1;
+            if result.is_successful()
+                || result.is_err()
+                || i == self.strategies.len()
+                || prover.get_cancellation_token().is_cancelled()
+            {
+                return (output, strategy);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Schedule, Strategy};
+    use crate::prover::axiom_selection::AxiomSelection;
+    use crate::prover::builder::Prover;
+    use crate::prover::clause_selection::heuristic::Heuristic;
+    use crate::prover::inference::literal_selection::LiteralSelection;
+    use crate::prover::proof_result::ProofResult;
+
+    #[test]
+    fn parse_1() {
+        let schedule = Schedule::parse(
+            "# A comment\n\
+             first\n\
+             \n\
+             second time=3 ordering=lpo renaming=0 heuristics=age:1 sine-depth=2 sos=on \
+             selection=complex\n",
+        )
+        .unwrap();
+        let strategies = schedule.get_strategies();
+
+        assert_eq!(strategies.len(), 2);
+        assert_eq!(strategies[0], Strategy::new("first"));
+        assert_eq!(strategies[1].get_name(), "second");
+        assert_eq!(strategies[1].get_time_share(), 3);
+        assert!(strategies[1].use_lpo);
+        assert_eq!(strategies[1].renaming_limit, 0);
+        assert_eq!(strategies[1].heuristics, vec![(Heuristic::Age, 1)]);
+        assert!(strategies[1].set_of_support);
+        assert_eq!(strategies[1].literal_selection, LiteralSelection::Complex);
+        assert_eq!(
+            strategies[1].axiom_selection,
+            AxiomSelection::Sine {
+                tolerance: 1.5,
+                depth: Some(2),
+                min_axioms: 64,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_2() {
+        assert!(Schedule::parse("# Only a comment").is_err());
+        assert!(Schedule::parse("a ordering").is_err());
+        assert!(Schedule::parse("a ordering=rpo").is_err());
+        assert!(Schedule::parse("a time=-1").is_err());
+        assert!(Schedule::parse("a\nb colour=blue").is_err());
+        assert_eq!(
+            Schedule::parse("a\nb sine=maybe"),
+            Err("Line 2: Invalid value maybe for sine".to_owned())
+        );
+    }
+
+    #[test]
+    fn default_schedule() {
+        assert!(Schedule::default().get_strategies().len() > 1);
+    }
+
+    #[test]
+    fn prove_str_1() {
+        // The axiom a is only triggered by r, which SInE never reaches, so the first strategy gives up.
+        let schedule = Schedule::parse(
+            "sine sine=on sine-min-axioms=0\n\
+             all sine=off\n",
+        )
+        .unwrap();
+        let problem = "fof(a, axiom, ![X]: (r(X) | p(X))). fof(b, axiom, ![X]: ~r(X)). \
+                       fof(c, axiom, p(c)). fof(d, axiom, p(d)). fof('Goal', conjecture, p(b)).";
+        let (output, strategy) = schedule.prove_str(&Prover::new().time_limit_ms(10000), problem);
+
+        assert_eq!(*output.get_result(), ProofResult::Theorem);
+        assert_eq!(strategy.get_name(), "all");
+    }
+
+    #[test]
+    fn prove_str_2() {
+        let schedule = Schedule::default();
+        let problem = "fof(a, axiom, p(a)). fof('Goal', conjecture, p(b)).";
+        let (output, strategy) = schedule.prove_str(&Prover::new(), problem);
+
+        assert_eq!(*output.get_result(), ProofResult::CounterSatisfiable);
+        assert_eq!(strategy.get_name(), "kbo");
+    }
+}
@@ -0,0 +1,496 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::cnf::renaming_info::{RenamingInfo, INT_SORT, RAT_SORT, REAL_SORT};
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::InferenceRule;
+use crate::prover::data_structures::literal::Literal;
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::HashMap;
+use num::{pow, BigInt, BigRational, One, Signed, Zero};
+use std::str::FromStr;
+
+/// Numbers with a larger exponent than this are left uninterpreted.
+const MAX_EXPONENT: u64 = 1000;
+
+/// The arithmetic functions and predicates of TPTP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+enum Operator {
+    UMinus,
+    Sum,
+    Difference,
+    Product,
+    Quotient,
+    QuotientE,
+    QuotientT,
+    QuotientF,
+    RemainderE,
+    RemainderT,
+    RemainderF,
+    Floor,
+    Ceiling,
+    Truncate,
+    Round,
+    ToInt,
+    ToRat,
+    ToReal,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    IsInt,
+    IsRat,
+}
+
+impl Operator {
+    /// Get the operator with the given TPTP name, if there is one.
+    fn from_name(s: &str) -> Option<Operator> {
+        match s {
+            "$uminus" => Some(Operator::UMinus),
+            "$sum" => Some(Operator::Sum),
+            "$difference" => Some(Operator::Difference),
+            "$product" => Some(Operator::Product),
+            "$quotient" => Some(Operator::Quotient),
+            "$quotient_e" => Some(Operator::QuotientE),
+            "$quotient_t" => Some(Operator::QuotientT),
+            "$quotient_f" => Some(Operator::QuotientF),
+            "$remainder_e" => Some(Operator::RemainderE),
+            "$remainder_t" => Some(Operator::RemainderT),
+            "$remainder_f" => Some(Operator::RemainderF),
+            "$floor" => Some(Operator::Floor),
+            "$ceiling" => Some(Operator::Ceiling),
+            "$truncate" => Some(Operator::Truncate),
+            "$round" => Some(Operator::Round),
+            "$to_int" => Some(Operator::ToInt),
+            "$to_rat" => Some(Operator::ToRat),
+            "$to_real" => Some(Operator::ToReal),
+            "$less" => Some(Operator::Less),
+            "$lesseq" => Some(Operator::LessEq),
+            "$greater" => Some(Operator::Greater),
+            "$greatereq" => Some(Operator::GreaterEq),
+            "$is_int" => Some(Operator::IsInt),
+            "$is_rat" => Some(Operator::IsRat),
+            _ => None,
+        }
+    }
+}
+
+/// Keeps track of the numbers and arithmetic symbols of a problem.
+/// Every value of a sort is represented by a single canonical constant.
+/// New constants are created for values computed during the search.
+#[derive(Debug, Default)]
+pub struct Arithmetic {
+    numbers: HashMap<i64, BigRational>,
+    canonical: HashMap<(i64, BigRational), i64>,
+    operators: HashMap<i64, Operator>,
+    new_numbers: Vec<(i64, String)>,
+    next_id: i64,
+    interpreted: bool,
+}
+
+impl Arithmetic {
+    /// Collects the numbers and arithmetic symbols of a problem.
+    /// Nothing is interpreted if the problem does not use arithmetic.
+    pub fn new(ri: &RenamingInfo) -> Arithmetic {
+        let mut arithmetic = Arithmetic {
+            next_id: ri.get_newest_function_id() + 1,
+            interpreted: ri.has_arithmetic()
+                && (ri.uses_sort(INT_SORT) || ri.uses_sort(RAT_SORT) || ri.uses_sort(REAL_SORT)),
+            ..Arithmetic::default()
+        };
+
+        if ri.has_arithmetic() {
+            for (id, s) in ri.iter_function_names() {
+                let sort = ri.get_function_sort(id);
+                if let Some(op) = Operator::from_name(s) {
+                    arithmetic.operators.insert(id, op);
+                } else if let (true, Some(value)) = (is_number_sort(sort), parse_number(s)) {
+                    // Pick the smallest ID so that the result does not depend on the iteration order.
+                    let canonical_id = arithmetic
+                        .canonical
+                        .entry((sort, value.clone()))
+                        .or_insert(id);
+                    *canonical_id = (*canonical_id).min(id);
+                    arithmetic.numbers.insert(id, value);
+                }
+            }
+        }
+
+        arithmetic
+    }
+
+    /// Checks if the problem uses any of the number sorts.
+    /// Since arithmetic is handled incompletely, saturation proves nothing in that case.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreted
+    }
+
+    /// Adds the names of the numbers created during the search to the renaming info.
+    pub fn name_new_numbers(&self, ri: &mut RenamingInfo) {
+        for &(id, ref s) in &self.new_numbers {
+            ri.add_function_name(id, s.clone());
+        }
+    }
+
+    /// Get the value of a term if it is a number.
+    fn get_value(&self, t: &Term) -> Option<&BigRational> {
+        if t.is_function() && t.get_arity() == 0 {
+            self.numbers.get(&t.get_id())
+        } else {
+            None
+        }
+    }
+
+    /// Get the canonical constant for a value of the given sort, creating it if necessary.
+    fn number_term(&mut self, value: BigRational, sort: i64) -> Term {
+        let id = if let Some(&id) = self.canonical.get(&(sort, value.clone())) {
+            id
+        } else {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.new_numbers.push((id, format_number(&value, sort)));
+            self.numbers.insert(id, value.clone());
+            self.canonical.insert((sort, value), id);
+            id
+        };
+        Term::new_sorted_function(id, sort, Vec::new())
+    }
+
+    /// Evaluates all ground arithmetic subterms of a term.
+    /// Returns true if the term was changed.
+    fn evaluate_term(&mut self, t: &mut Term) -> bool {
+        if t.is_variable() {
+            return false;
+        }
+
+        let mut changed = false;
+        let mut args = t.iter().cloned().collect::<Vec<_>>();
+        for arg in &mut args {
+            changed |= self.evaluate_term(arg);
+        }
+        if changed {
+            *t = t.with_args(args);
+        }
+
+        let value = if let Some(value) = self.get_value(t) {
+            Some(value.clone())
+        } else if let (Some(&op), false) =
+            (self.operators.get(&t.get_id()), t.is_special_function())
+        {
+            let args = t
+                .iter()
+                .map(|arg| self.get_value(arg))
+                .collect::<Option<Vec<_>>>();
+            args.and_then(|args| evaluate_function(op, &args, t[0].get_sort()))
+        } else {
+            None
+        };
+
+        if let Some(value) = value {
+            let new_t = self.number_term(value, t.get_sort());
+            if new_t != *t {
+                *t = new_t;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Decides the truth value of a literal if it is ground arithmetic.
+    fn decide_literal(&self, l: &Literal) -> Option<bool> {
+        let (lhs, rhs) = if l.get_lhs().is_truth() {
+            (l.get_rhs(), l.get_lhs())
+        } else {
+            (l.get_lhs(), l.get_rhs())
+        };
+
+        let holds = if rhs.is_truth() {
+            let op = *self.operators.get(&lhs.get_id())?;
+            let args = lhs
+                .iter()
+                .map(|arg| self.get_value(arg))
+                .collect::<Option<Vec<_>>>()?;
+            evaluate_predicate(op, &args)?
+        } else {
+            self.get_value(lhs)? == self.get_value(rhs)?
+        };
+
+        Some(holds == l.is_positive())
+    }
+}
+
+/// Evaluates ground arithmetic subterms and decides ground arithmetic literals in a clause.
+/// False literals are deleted, while true literals are replaced with T = T.
+/// The clause is then deleted as a tautology.
+pub fn evaluate_arithmetic(arithmetic: &mut Arithmetic, cl: &mut Clause) {
+    if arithmetic.numbers.is_empty() && arithmetic.operators.is_empty() {
+        return;
+    }
+
+    let mut changed = false;
+    for l in cl.iter_mut() {
+        changed |= arithmetic.evaluate_term(l.get_lhs_mut());
+        changed |= arithmetic.evaluate_term(l.get_rhs_mut());
+    }
+
+    let mut i = 0;
+    while i < cl.size() {
+        match arithmetic.decide_literal(&cl[i]) {
+            Some(true) => {
+                cl[i] = Literal::new(false, Term::new_truth(), Term::new_truth());
+                changed = true;
+                break;
+            }
+            Some(false) => {
+                cl.swap_remove(i);
+                changed = true;
+            }
+            None => i += 1,
+        }
+    }
+
+    if changed {
+        cl.add_simplification(InferenceRule::Evaluation, Vec::new());
+    }
+}
+
+/// Checks if a sort is one of the number sorts.
+fn is_number_sort(sort: i64) -> bool {
+    sort == INT_SORT || sort == RAT_SORT || sort == REAL_SORT
+}
+
+/// Evaluates an arithmetic function, if it is defined for the arguments.
+fn evaluate_function(op: Operator, args: &[&BigRational], arg_sort: i64) -> Option<BigRational> {
+    let x = args[0];
+    let y = args.get(1).cloned();
+    match op {
+        Operator::UMinus => Some(-x),
+        Operator::Sum => Some(x + y?),
+        Operator::Difference => Some(x - y?),
+        Operator::Product => Some(x * y?),
+        Operator::Quotient if arg_sort != INT_SORT && !y?.is_zero() => Some(x / y?),
+        Operator::QuotientE | Operator::QuotientT | Operator::QuotientF => {
+            integer_quotient(op, x, y?)
+        }
+        Operator::RemainderE | Operator::RemainderT | Operator::RemainderF => {
+            let y = y?;
+            integer_quotient(op, x, y).map(|q| x - q * y)
+        }
+        Operator::Floor | Operator::ToInt => Some(x.floor()),
+        Operator::Ceiling => Some(x.ceil()),
+        Operator::Truncate => Some(x.trunc()),
+        // TPTP does not say which way ties are rounded, so those are left alone.
+        Operator::Round if x.fract().abs() != BigRational::new(One::one(), BigInt::from(2)) => {
+            Some(x.round())
+        }
+        Operator::ToRat | Operator::ToReal => Some(x.clone()),
+        _ => None,
+    }
+}
+
+/// Calculates the quotient of x and y rounded as the operator says.
+/// Euclidean division is used for the E-versions, truncation for T and flooring for F.
+fn integer_quotient(op: Operator, x: &BigRational, y: &BigRational) -> Option<BigRational> {
+    if y.is_zero() {
+        return None;
+    }
+
+    let q = x / y;
+    match op {
+        Operator::QuotientT | Operator::RemainderT => Some(q.trunc()),
+        Operator::QuotientF | Operator::RemainderF => Some(q.floor()),
+        Operator::QuotientE | Operator::RemainderE => {
+            if y.is_positive() {
+                Some(q.floor())
+            } else {
+                Some(q.ceil())
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Evaluates an arithmetic predicate, if it is defined for the arguments.
+fn evaluate_predicate(op: Operator, args: &[&BigRational]) -> Option<bool> {
+    match op {
+        Operator::Less => Some(args[0] < *args.get(1)?),
+        Operator::LessEq => Some(args[0] <= *args.get(1)?),
+        Operator::Greater => Some(args[0] > *args.get(1)?),
+        Operator::GreaterEq => Some(args[0] >= *args.get(1)?),
+        Operator::IsInt => Some(args[0].is_integer()),
+        Operator::IsRat => Some(true),
+        _ => None,
+    }
+}
+
+/// Parses an integer, a rational or a real in TPTP syntax.
+fn parse_number(s: &str) -> Option<BigRational> {
+    let s = s.strip_prefix('+').unwrap_or(s);
+    if let Some(pos) = s.find('/') {
+        let n = BigInt::from_str(&s[..pos]).ok()?;
+        let d = BigInt::from_str(&s[(pos + 1)..]).ok()?;
+        if d.is_zero() {
+            None
+        } else {
+            Some(BigRational::new(n, d))
+        }
+    } else if let Some(pos) = s.find('.') {
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(e_pos) => {
+                let e = &s[(e_pos + 1)..];
+                (
+                    &s[..e_pos],
+                    i64::from_str(e.strip_prefix('+').unwrap_or(e)).ok()?,
+                )
+            }
+            None => (s, 0),
+        };
+        let fraction = &mantissa[(pos + 1)..];
+        let digits = BigInt::from_str(&format!("{}{}", &mantissa[..pos], fraction)).ok()?;
+        let exponent = exponent - fraction.len() as i64;
+        if exponent.unsigned_abs() > MAX_EXPONENT {
+            return None;
+        }
+        let scale = pow(BigInt::from(10), exponent.unsigned_abs() as usize);
+        if exponent >= 0 {
+            Some(BigRational::from_integer(digits * scale))
+        } else {
+            Some(BigRational::new(digits, scale))
+        }
+    } else {
+        BigInt::from_str(s).ok().map(BigRational::from_integer)
+    }
+}
+
+/// Formats a value of the given sort in TPTP syntax.
+/// Reals which have no finite decimal representation are written as rationals.
+fn format_number(value: &BigRational, sort: i64) -> String {
+    if sort == INT_SORT {
+        value.to_integer().to_string()
+    } else if sort == RAT_SORT {
+        format!("{}/{}", value.numer(), value.denom())
+    } else {
+        // The decimal representation is finite if the denominator only has the factors 2 and 5.
+        let two = BigInt::from(2);
+        let five = BigInt::from(5);
+        let mut denom = value.denom().clone();
+        let (mut twos, mut fives) = (0, 0);
+        while (&denom % &two).is_zero() {
+            denom /= &two;
+            twos += 1;
+        }
+        while (&denom % &five).is_zero() {
+            denom /= &five;
+            fives += 1;
+        }
+
+        if !denom.is_one() {
+            return format!("{}/{}", value.numer(), value.denom());
+        }
+
+        let decimals = twos.max(fives);
+        let digits = (value.abs() * BigRational::from_integer(pow(BigInt::from(10), decimals)))
+            .to_integer()
+            .to_string();
+        let digits = format!("{:0>width$}", digits, width = decimals + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - decimals);
+        let sign = if value.is_negative() { "-" } else { "" };
+        if frac_part.is_empty() {
+            format!("{}{}.0", sign, int_part)
+        } else {
+            format!("{}{}.{}", sign, int_part, frac_part)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{evaluate_arithmetic, format_number, parse_number, Arithmetic};
+    use crate::cnf::renaming_info::{RenamingInfo, INT_SORT, RAT_SORT, REAL_SORT};
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+    use num::{BigInt, BigRational};
+
+    fn rational(n: i64, d: i64) -> BigRational {
+        BigRational::new(BigInt::from(n), BigInt::from(d))
+    }
+
+    #[test]
+    fn parse_number_1() {
+        assert_eq!(parse_number("-12"), Some(rational(-12, 1)));
+        assert_eq!(parse_number("+6/4"), Some(rational(3, 2)));
+        assert_eq!(parse_number("1/0"), None);
+        assert_eq!(parse_number("-0.25"), Some(rational(-1, 4)));
+        assert_eq!(parse_number("1.5E+2"), Some(rational(150, 1)));
+        assert_eq!(parse_number("2.5e-1"), Some(rational(1, 4)));
+    }
+
+    #[test]
+    fn format_number_1() {
+        assert_eq!(format_number(&rational(-12, 1), INT_SORT), "-12");
+        assert_eq!(format_number(&rational(3, 2), RAT_SORT), "3/2");
+        assert_eq!(format_number(&rational(-1, 40), REAL_SORT), "-0.025");
+        assert_eq!(format_number(&rational(3, 1), REAL_SORT), "3.0");
+        assert_eq!(format_number(&rational(1, 3), REAL_SORT), "1/3");
+    }
+
+    #[test]
+    fn evaluate_arithmetic_1() {
+        // $remainder_e(-7, 2) != 1 should be false and $quotient_t(-7, 2) != -7 true.
+        let mut ri = RenamingInfo::new();
+        ri.enable_arithmetic();
+        let minus_seven = ri.declare_function("-7".to_owned(), &[], "$int");
+        let two = ri.declare_function("2".to_owned(), &[], "$int");
+        let one = ri.declare_function("1".to_owned(), &[], "$int");
+        let rem = ri.get_arithmetic_function_id("$remainder_e".to_owned(), 2, INT_SORT, INT_SORT);
+        let quot = ri.get_arithmetic_function_id("$quotient_t".to_owned(), 2, INT_SORT, INT_SORT);
+        let mut arithmetic = Arithmetic::new(&ri);
+
+        let int = |id| Term::new_sorted_function(id, INT_SORT, Vec::new());
+        let rem_t = Term::new_sorted_function(rem, INT_SORT, vec![int(minus_seven), int(two)]);
+        let mut cl = Clause::new(vec![Literal::new(true, rem_t, int(one))]);
+        evaluate_arithmetic(&mut arithmetic, &mut cl);
+        assert!(cl.is_empty());
+
+        let quot_t = Term::new_sorted_function(quot, INT_SORT, vec![int(minus_seven), int(two)]);
+        let mut cl = Clause::new(vec![Literal::new(true, quot_t, int(minus_seven))]);
+        evaluate_arithmetic(&mut arithmetic, &mut cl);
+        assert_eq!(cl.size(), 1);
+        assert!(cl[0].get_lhs().is_truth());
+
+        // The quotient -3 was not in the problem so it must have been created.
+        let mut new_ri = ri.clone();
+        arithmetic.name_new_numbers(&mut new_ri);
+        assert_eq!(new_ri.get_function_name(quot + 1), Some("-3"));
+    }
+
+    #[test]
+    fn evaluate_arithmetic_2() {
+        // Numbers are uninterpreted in untyped problems.
+        let mut ri = RenamingInfo::new();
+        let one = ri.get_function_id("1".to_owned(), 0, false);
+        let two = ri.get_function_id("2".to_owned(), 0, false);
+        let mut arithmetic = Arithmetic::new(&ri);
+
+        let l = Literal::new(false, Term::new_constant(one), Term::new_constant(two));
+        let mut cl = Clause::new(vec![l]);
+        evaluate_arithmetic(&mut arithmetic, &mut cl);
+        assert_eq!(cl.size(), 1);
+    }
+}
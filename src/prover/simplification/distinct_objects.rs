@@ -0,0 +1,89 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::InferenceRule;
+use crate::prover::data_structures::literal::Literal;
+
+/// Checks if a literal is an equation between two different distinct objects.
+fn equates_distinct_objects(l: &Literal) -> bool {
+    l.get_lhs().is_distinct_object()
+        && l.get_rhs().is_distinct_object()
+        && l.get_lhs() != l.get_rhs()
+}
+
+/// Deletes all literals of the form "a" = "b", as distinct objects are never equal.
+pub fn delete_distinct_object_equations(cl: &mut Clause) {
+    let mut deleted = false;
+    let mut i = 0;
+
+    while i < cl.size() {
+        if cl[i].is_positive() && equates_distinct_objects(&cl[i]) {
+            cl.swap_remove(i);
+            deleted = true;
+            continue;
+        }
+        i += 1;
+    }
+
+    if deleted {
+        cl.add_simplification(InferenceRule::DistinctObjects, Vec::new());
+    }
+}
+
+/// Checks if a clause contains a literal of the form "a" <> "b", which is always true.
+pub fn distinct_objects_unequal(cl: &Clause) -> bool {
+    cl.iter()
+        .any(|l| l.is_negative() && equates_distinct_objects(l))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{delete_distinct_object_equations, distinct_objects_unequal};
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+
+    #[test]
+    fn delete_distinct_object_equations_1() {
+        let a = Term::new_distinct_object(1);
+        let b = Term::new_distinct_object(2);
+        let c = Term::new_constant(3);
+        let l1 = Literal::new(false, a.clone(), b.clone());
+        let l2 = Literal::new(false, a.clone(), c);
+        let l3 = Literal::new(false, b.clone(), b);
+        let mut cl = Clause::new(vec![l1, l2.clone(), l3.clone()]);
+        delete_distinct_object_equations(&mut cl);
+
+        assert_eq!(cl.size(), 2);
+        assert!(cl.iter().any(|l| *l == l2));
+        assert!(cl.iter().any(|l| *l == l3));
+    }
+
+    #[test]
+    fn distinct_objects_unequal_1() {
+        let a = Term::new_distinct_object(1);
+        let b = Term::new_distinct_object(2);
+        let c = Term::new_constant(3);
+        let cl1 = Clause::new(vec![Literal::new(true, a.clone(), c)]);
+        let cl2 = Clause::new(vec![Literal::new(true, a.clone(), a.clone())]);
+        let cl3 = Clause::new(vec![Literal::new(true, b, a)]);
+
+        assert!(!distinct_objects_unequal(&cl1));
+        assert!(!distinct_objects_unequal(&cl2));
+        assert!(distinct_objects_unequal(&cl3));
+    }
+}
@@ -21,26 +21,41 @@ use crate::prover::data_structures::term::Term;
 use crate::prover::unification::matching::term_match_with_subst;
 
 /// Check if the term index contains a matching literal without taking into account symmetricity.
-fn matching_equation_exists_asymmetric(term_index: &PDTree, s: &Term, t: &Term, pos: bool) -> bool {
-    for (_, r, sigma, _) in term_index.iter_generalizations(s, pos) {
+/// Returns the ID of the clause the matching literal comes from.
+fn find_matching_equation_asymmetric(
+    term_index: &PDTree,
+    s: &Term,
+    t: &Term,
+    pos: bool,
+) -> Option<u64> {
+    for (_, r, sigma, _, id) in term_index.iter_generalizations(s, pos) {
         if term_match_with_subst(sigma, r, t).is_some() {
-            return true;
+            return Some(id);
         }
     }
 
-    false
+    None
+}
+
+/// Same as `matching_equation_exists`, except that we return the ID of the clause matching.
+pub fn find_matching_equation(term_index: &PDTree, s: &Term, t: &Term, pos: bool) -> Option<u64> {
+    find_matching_equation_asymmetric(term_index, s, t, pos)
+        .or_else(|| find_matching_equation_asymmetric(term_index, t, s, pos))
 }
 
 // TODO: figure out a better name
 pub fn matching_equation_exists(term_index: &PDTree, s: &Term, t: &Term, pos: bool) -> bool {
-    matching_equation_exists_asymmetric(term_index, s, t, pos)
-        || matching_equation_exists_asymmetric(term_index, t, s, pos)
+    find_matching_equation(term_index, s, t, pos).is_some()
 }
 
 /// Checks if we can make s = t by using some unit clause in the term index.
+/// The ID of the unit clause used, if any, is added to `premises`.
 // TODO: remove pub, rename into something more suited.
-pub fn equation_subsumed(term_index: &PDTree, s: &Term, t: &Term) -> bool {
-    if s == t || matching_equation_exists(term_index, s, t, true) {
+pub fn equation_subsumed(term_index: &PDTree, s: &Term, t: &Term, premises: &mut Vec<u64>) -> bool {
+    if s == t {
+        true
+    } else if let Some(id) = find_matching_equation(term_index, s, t, true) {
+        premises.push(id);
         true
     } else if s.is_function() && s.get_id() == t.get_id() {
         assert_eq!(s.get_arity(), t.get_arity());
@@ -57,7 +72,7 @@ pub fn equation_subsumed(term_index: &PDTree, s: &Term, t: &Term) -> bool {
         }
 
         if let Some(i) = diff_index {
-            equation_subsumed(term_index, &s[i], &t[i])
+            equation_subsumed(term_index, &s[i], &t[i], premises)
         } else {
             panic!("At least one location should be different");
         }
@@ -69,7 +84,7 @@ pub fn equation_subsumed(term_index: &PDTree, s: &Term, t: &Term) -> bool {
 /// Checks if a given literal is equality subsumed by some unit clause in the term index.
 fn literal_subsumed(term_index: &PDTree, l: &Literal) -> bool {
     if l.is_positive() {
-        equation_subsumed(term_index, l.get_lhs(), l.get_rhs())
+        equation_subsumed(term_index, l.get_lhs(), l.get_rhs(), &mut Vec::new())
     } else {
         // Positive simplify-reflect could be done here
         false
@@ -34,3 +34,9 @@ pub mod literal_deletion;
 
 /// Contains functions for detecting tautologies.
 pub mod tautology_deletion;
+
+/// Contains functions for simplifying equations between distinct objects.
+pub mod distinct_objects;
+
+/// Contains functions for evaluating ground arithmetic terms and literals.
+pub mod arithmetic;
@@ -157,7 +157,10 @@ fn update_function_symbol_count_in_term(counts: &mut HashMap<i64, i64>, t: &Term
 /// We use multiset subsumption instead of set subsumption to prevent some undesirable effects.
 /// An example is the possibility of a clause subsuming its factors.
 /// Time complexity is O(n! * 2^n) which is kinda ridiculous. In practice n is small (<=5) though.
-pub fn non_unit_subsumed(active: &[Clause], cl: &Clause) -> bool {
+pub fn non_unit_subsumed<'a, I>(active: I, cl: &Clause) -> bool
+where
+    I: IntoIterator<Item = &'a Clause>,
+{
     for act_cl in active {
         if fulfills_preconditions(act_cl, cl) {
             let mut exclusion = vec![false; cl.size()];
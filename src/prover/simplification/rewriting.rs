@@ -15,26 +15,36 @@
 //
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::InferenceRule;
 use crate::prover::data_structures::pd_tree::PDTree;
 use crate::prover::data_structures::term::Term;
-use crate::prover::ordering::term_ordering::TermOrdering;
+use crate::prover::ordering::term_ordering::{Comparison, TermOrdering};
 
 /// Rewrites a term into some normal form.
 /// Tries to rewrite the leftmost and innermost terms first.
+/// The IDs of the unit clauses used for rewriting are added to `premises`.
 fn rewrite_to_normal_form(
     term_ordering: &TermOrdering,
     term_index: &PDTree,
     t: &mut Term,
     restrict: bool,
+    premises: &mut Vec<u64>,
 ) {
     if t.is_function() {
-        for sub_t in t.iter_mut() {
-            rewrite_to_normal_form(term_ordering, term_index, sub_t, false);
+        let premise_count = premises.len();
+        let mut args = t.iter().cloned().collect::<Vec<_>>();
+        for sub_t in &mut args {
+            rewrite_to_normal_form(term_ordering, term_index, sub_t, false, premises);
+        }
+        // Terms are immutable, so the term is rebuilt if any of the arguments were rewritten.
+        if premises.len() > premise_count {
+            *t = t.with_args(args);
         }
 
-        if let Some(new_r) = normal_form_step_helper(term_ordering, term_index, t, restrict) {
+        if let Some((new_r, id)) = normal_form_step_helper(term_ordering, term_index, t, restrict) {
             *t = new_r;
-            rewrite_to_normal_form(term_ordering, term_index, t, restrict);
+            premises.push(id);
+            rewrite_to_normal_form(term_ordering, term_index, t, restrict, premises);
         }
     }
 }
@@ -45,13 +55,13 @@ fn normal_form_step_helper(
     term_index: &PDTree,
     t: &Term,
     restrict: bool,
-) -> Option<Term> {
-    for (_, r, sigma, oriented) in term_index.iter_generalizations(t, true) {
+) -> Option<(Term, u64)> {
+    for (_, r, sigma, oriented, id) in term_index.iter_generalizations(t, true) {
         if !restrict || !sigma.is_variable_renaming() {
             let mut new_r = r.clone();
             new_r.subst(&sigma);
             if oriented || term_ordering.gt(t, &new_r) {
-                return Some(new_r);
+                return Some((new_r, id));
             }
         }
     }
@@ -79,22 +89,58 @@ fn maximal_literals(term_ordering: &TermOrdering, cl: &Clause) -> Vec<bool> {
 }
 
 /// Rewrites a given clause into normal form with regards to the active clause set.
-pub fn rewrite_clause(term_ordering: &TermOrdering, term_index: &PDTree, cl: &mut Clause) {
+/// Returns true if the clause was rewritten.
+pub fn rewrite_clause(term_ordering: &TermOrdering, term_index: &PDTree, cl: &mut Clause) -> bool {
     let bv = maximal_literals(term_ordering, cl);
+    let mut premises = Vec::new();
 
     for i in 0..cl.size() {
         let l = &mut cl[i];
         let restricted = l.is_positive() && bv[i];
         if restricted {
-            let s_t = term_ordering.gt(l.get_lhs(), l.get_rhs());
-            let t_s = term_ordering.gt(l.get_rhs(), l.get_lhs());
-            rewrite_to_normal_form(term_ordering, term_index, l.get_lhs_mut(), s_t);
-            rewrite_to_normal_form(term_ordering, term_index, l.get_rhs_mut(), t_s);
+            let comparison = term_ordering.compare(l.get_lhs(), l.get_rhs());
+            let s_t = comparison == Comparison::Greater;
+            let t_s = comparison == Comparison::Less;
+            rewrite_to_normal_form(
+                term_ordering,
+                term_index,
+                l.get_lhs_mut(),
+                s_t,
+                &mut premises,
+            );
+            rewrite_to_normal_form(
+                term_ordering,
+                term_index,
+                l.get_rhs_mut(),
+                t_s,
+                &mut premises,
+            );
         } else {
-            rewrite_to_normal_form(term_ordering, term_index, l.get_lhs_mut(), false);
-            rewrite_to_normal_form(term_ordering, term_index, l.get_rhs_mut(), false);
+            rewrite_to_normal_form(
+                term_ordering,
+                term_index,
+                l.get_lhs_mut(),
+                false,
+                &mut premises,
+            );
+            rewrite_to_normal_form(
+                term_ordering,
+                term_index,
+                l.get_rhs_mut(),
+                false,
+                &mut premises,
+            );
         }
     }
+
+    if premises.is_empty() {
+        false
+    } else {
+        premises.sort();
+        premises.dedup();
+        cl.add_simplification(InferenceRule::Rewriting, premises);
+        true
+    }
 }
 
 #[cfg(test)]
@@ -15,31 +15,45 @@
 //
 
 use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::InferenceRule;
 use crate::prover::data_structures::literal::Literal;
 use crate::prover::data_structures::pd_tree::PDTree;
 use crate::prover::simplification::equality_subsumption::{
-    equation_subsumed, matching_equation_exists,
+    equation_subsumed, find_matching_equation,
 };
 
 /// Simplifies a given clause by positive and negative simplify-reflect.
 pub fn simplify_reflect(term_index: &PDTree, cl: &mut Clause) {
+    let mut premises = Vec::new();
     let mut i = 0;
 
     while i < cl.size() {
-        if simplifiable(term_index, &cl[i]) {
+        if simplifiable(term_index, &cl[i], &mut premises) {
             cl.swap_remove(i);
             continue;
         }
 
         i += 1;
     }
+
+    if !premises.is_empty() {
+        premises.sort();
+        premises.dedup();
+        cl.add_simplification(InferenceRule::SimplifyReflect, premises);
+    }
 }
 
-fn simplifiable(term_index: &PDTree, l: &Literal) -> bool {
+/// Checks if a literal can be deleted. The IDs of the unit clauses needed are added to `premises`.
+fn simplifiable(term_index: &PDTree, l: &Literal, premises: &mut Vec<u64>) -> bool {
     if l.is_positive() {
-        matching_equation_exists(term_index, l.get_lhs(), l.get_rhs(), false)
+        if let Some(id) = find_matching_equation(term_index, l.get_lhs(), l.get_rhs(), false) {
+            premises.push(id);
+            true
+        } else {
+            false
+        }
     } else {
-        equation_subsumed(term_index, l.get_lhs(), l.get_rhs())
+        equation_subsumed(term_index, l.get_lhs(), l.get_rhs(), premises)
     }
 }
 
@@ -0,0 +1,399 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::cnf::renaming_info::RenamingInfo;
+use crate::prover::data_structures::clause::Clause;
+use crate::prover::data_structures::derivation::Derivation;
+use crate::prover::data_structures::literal::Literal;
+use crate::prover::data_structures::term::Term;
+use crate::utils::hash_map::{HashMap, HashSet};
+
+/// Checks if a symbol can be printed as is, i.e. without single quotes.
+/// Distinct objects already contain their double quotes.
+fn is_plain_symbol(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some('"') => s.len() > 1 && s.ends_with('"'),
+        Some(c) if c.is_ascii_lowercase() || c == '$' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        Some(c) if c.is_ascii_digit() || c == '+' || c == '-' => s.chars().skip(1).all(|c| {
+            c.is_ascii_digit()
+                || c == '.'
+                || c == '/'
+                || c == 'e'
+                || c == 'E'
+                || c == '-'
+                || c == '+'
+        }),
+        _ => false,
+    }
+}
+
+/// Converts a function or predicate ID back into its (possibly quoted) TPTP name.
+fn format_symbol(renaming_info: &RenamingInfo, id: i64) -> String {
+    match renaming_info.get_function_name(id) {
+        Some(s) if is_plain_symbol(s) => s.to_owned(),
+        Some(s) => format!("'{}'", s),
+        None => format!("f{}", id),
+    }
+}
+
+/// Formats a term in TPTP syntax.
+/// Variables are numbered in the order they appear in the clause.
+fn format_term(
+    renaming_info: &RenamingInfo,
+    var_names: &mut HashMap<i64, usize>,
+    t: &Term,
+) -> String {
+    if t.is_variable() {
+        let next_var = var_names.len();
+        format!("X{}", var_names.entry(t.get_id()).or_insert(next_var))
+    } else if t.is_truth() {
+        "$true".to_owned()
+    } else if t.get_arity() == 0 {
+        format_symbol(renaming_info, t.get_id())
+    } else {
+        let args = t
+            .iter()
+            .map(|st| format_term(renaming_info, var_names, st))
+            .collect::<Vec<_>>();
+        format!(
+            "{}({})",
+            format_symbol(renaming_info, t.get_id()),
+            args.join(",")
+        )
+    }
+}
+
+/// Formats a literal in TPTP syntax.
+/// Literals of the form P = T are turned back into predicates.
+fn format_literal(
+    renaming_info: &RenamingInfo,
+    var_names: &mut HashMap<i64, usize>,
+    l: &Literal,
+) -> String {
+    let lhs = format_term(renaming_info, var_names, l.get_lhs());
+    let rhs = format_term(renaming_info, var_names, l.get_rhs());
+    let sign = if l.is_negative() { "~" } else { "" };
+
+    if l.get_rhs().is_truth() {
+        format!("{}{}", sign, lhs)
+    } else if l.get_lhs().is_truth() {
+        format!("{}{}", sign, rhs)
+    } else if l.is_negative() {
+        format!("{} != {}", lhs, rhs)
+    } else {
+        format!("{} = {}", lhs, rhs)
+    }
+}
+
+/// Formats the literals of a clause in TPTP syntax.
+pub fn format_clause(renaming_info: &RenamingInfo, cl: &Clause) -> String {
+    if cl.is_empty() {
+        "$false".to_owned()
+    } else {
+        let mut var_names = HashMap::default();
+        let literals = cl
+            .iter()
+            .map(|l| format_literal(renaming_info, &mut var_names, l))
+            .collect::<Vec<_>>();
+        format!("( {} )", literals.join(" | "))
+    }
+}
+
+/// Formats the name of an input formula, quoting it if necessary.
+fn format_name(s: &str) -> String {
+    let plain_word = s.starts_with(|c: char| c.is_ascii_lowercase())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain_word || (!s.is_empty() && s.chars().all(|c| c.is_ascii_digit())) {
+        s.to_owned()
+    } else {
+        format!("'{}'", s)
+    }
+}
+
+/// Formats the source of an input clause, referring to the input formulas it was created from.
+fn format_input(renaming_info: &RenamingInfo, origins: &[usize], input_file: &str) -> String {
+    let sources = if origins.is_empty() {
+        vec![format!("file('{}')", input_file)]
+    } else {
+        origins
+            .iter()
+            .map(|&id| {
+                let origin = renaming_info.get_formula_origin(id);
+                format!(
+                    "file('{}',{})",
+                    origin.get_file().unwrap_or(input_file),
+                    format_name(origin.get_name())
+                )
+            })
+            .collect()
+    };
+    format!(
+        "inference(cnf_transformation,[status(esa)],[{}])",
+        sources.join(",")
+    )
+}
+
+/// Input clauses get the role of the formula they came from.
+/// Conjectures are negated before they are transformed into clauses.
+/// Clauses given directly to the prover have no origins, so they are treated as axioms.
+fn format_role<'a>(renaming_info: &'a RenamingInfo, origins: &[usize]) -> &'a str {
+    match origins.first() {
+        Some(&id) if renaming_info.get_formula_origin(id).is_conjecture() => "negated_conjecture",
+        Some(&id) => renaming_info.get_formula_origin(id).get_role(),
+        None => "axiom",
+    }
+}
+
+/// Formats the derivation of a clause which is not an input clause.
+/// The input clause a simplified input clause started from is referred to with the ID of the clause.
+fn format_derivation(derivation: &Derivation, id: u64) -> String {
+    match *derivation {
+        Derivation::Input => format!("i_{}", id),
+        Derivation::Clause(parent_id) => format!("c_{}", parent_id),
+        Derivation::Inference(rule, ref premises) => {
+            let parents = premises
+                .iter()
+                .map(|p| format_derivation(p, id))
+                .collect::<Vec<_>>();
+            format!(
+                "inference({},[status(thm)],[{}])",
+                rule.tstp_name(),
+                parents.join(",")
+            )
+        }
+    }
+}
+
+/// Formats an input clause as an annotated TSTP formula with the given name.
+fn format_input_clause(
+    renaming_info: &RenamingInfo,
+    name: &str,
+    cl: &Clause,
+    input_file: &str,
+) -> String {
+    format!(
+        "cnf({},{},{},{}).",
+        name,
+        format_role(renaming_info, cl.get_origins()),
+        format_clause(renaming_info, cl),
+        format_input(renaming_info, cl.get_origins(), input_file)
+    )
+}
+
+/// Formats a single clause as TSTP lines.
+/// A simplified input clause is preceded by the input clause it started from.
+fn format_annotated_clause(
+    renaming_info: &RenamingInfo,
+    inputs: &HashMap<u64, Clause>,
+    cl: &Clause,
+    input_file: &str,
+    lines: &mut Vec<String>,
+) {
+    let id = cl.get_id();
+    if *cl.get_derivation() == Derivation::Input {
+        lines.push(format_input_clause(
+            renaming_info,
+            &format!("c_{}", id),
+            cl,
+            input_file,
+        ));
+    } else {
+        if cl.get_derivation().has_input() {
+            let input_cl = inputs
+                .get(&id)
+                .expect("Simplified input clauses should have an input version");
+            lines.push(format_input_clause(
+                renaming_info,
+                &format!("i_{}", id),
+                input_cl,
+                input_file,
+            ));
+        }
+        lines.push(format!(
+            "cnf(c_{},plain,{},{}).",
+            id,
+            format_clause(renaming_info, cl),
+            format_derivation(cl.get_derivation(), id)
+        ));
+    }
+}
+
+/// Walks back from the empty clause through the derivations of the used clauses.
+/// Returns the IDs of all clauses the refutation depends on, with every clause coming after its parents.
+/// The IDs alone do not give such an order, as a clause keeps its ID when it is simplified by newer clauses.
+fn refutation_clause_ids(used: &HashMap<u64, Clause>, empty_clause: &Clause) -> Vec<u64> {
+    let mut order = Vec::new();
+    let mut visited = HashSet::default();
+    // A clause is pushed a second time once its parents are on the stack above it.
+    let mut stack = Vec::new();
+    let mut parents = Vec::new();
+    empty_clause.get_derivation().parent_ids(&mut parents);
+    stack.extend(parents.drain(..).rev().map(|id| (id, false)));
+
+    while let Some((id, parents_done)) = stack.pop() {
+        if parents_done {
+            order.push(id);
+        } else if visited.insert(id) {
+            stack.push((id, true));
+            let cl = used
+                .get(&id)
+                .expect("Parent clauses should always be used clauses");
+            cl.get_derivation().parent_ids(&mut parents);
+            stack.extend(
+                parents
+                    .drain(..)
+                    .rev()
+                    .filter(|p| !visited.contains(p))
+                    .map(|p| (p, false)),
+            );
+        }
+    }
+
+    order
+}
+
+/// Returns the proof as TSTP lines, with every clause coming after its parents.
+/// The input versions of the used clauses are needed for clauses which were simplified in place.
+pub fn refutation_to_tstp(
+    renaming_info: &RenamingInfo,
+    used: &HashMap<u64, Clause>,
+    inputs: &HashMap<u64, Clause>,
+    empty_clause: &Clause,
+    input_file: &str,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for id in refutation_clause_ids(used, empty_clause) {
+        format_annotated_clause(renaming_info, inputs, &used[&id], input_file, &mut lines);
+    }
+    format_annotated_clause(renaming_info, inputs, empty_clause, input_file, &mut lines);
+    lines
+}
+
+/// Returns the names of the input formulas the refutation depends on, in the order of the input.
+pub fn refutation_to_unsat_core(
+    renaming_info: &RenamingInfo,
+    used: &HashMap<u64, Clause>,
+    empty_clause: &Clause,
+) -> Vec<String> {
+    let mut origins = empty_clause.get_origins().to_vec();
+    for id in refutation_clause_ids(used, empty_clause) {
+        origins.extend_from_slice(used[&id].get_origins());
+    }
+    origins_to_unsat_core(renaming_info, origins)
+}
+
+/// Returns the names of the input formulas with the given origin IDs, in the order of the input.
+pub fn origins_to_unsat_core(renaming_info: &RenamingInfo, mut origins: Vec<usize>) -> Vec<String> {
+    origins.sort_unstable();
+    origins.dedup();
+    origins
+        .into_iter()
+        .map(|id| format_name(renaming_info.get_formula_origin(id).get_name()))
+        .collect()
+}
+
+/// Formats a saturated clause set as TSTP lines.
+/// The sources are left out as the parents of a clause might not be part of the set.
+pub fn saturation_to_tstp(renaming_info: &RenamingInfo, clauses: &[&Clause]) -> Vec<String> {
+    clauses
+        .iter()
+        .map(|cl| {
+            format!(
+                "cnf(c_{},plain,{}).",
+                cl.get_id(),
+                format_clause(renaming_info, cl)
+            )
+        })
+        .collect()
+}
+
+/// The proof when the CNF transformation already reduced the problem to false.
+pub fn trivial_refutation_to_tstp(
+    renaming_info: &RenamingInfo,
+    origins: &[usize],
+    input_file: &str,
+) -> Vec<String> {
+    vec![format!(
+        "cnf(c_0,{},$false,{}).",
+        format_role(renaming_info, origins),
+        format_input(renaming_info, origins, input_file)
+    )]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_clause, is_plain_symbol, saturation_to_tstp};
+    use crate::cnf::renaming_info::RenamingInfo;
+    use crate::prover::data_structures::clause::Clause;
+    use crate::prover::data_structures::literal::Literal;
+    use crate::prover::data_structures::term::Term;
+
+    #[test]
+    fn is_plain_symbol_1() {
+        assert!(is_plain_symbol("f"));
+        assert!(is_plain_symbol("a_Constant1"));
+        assert!(is_plain_symbol("$true"));
+        assert!(is_plain_symbol("-12"));
+        assert!(!is_plain_symbol("A constant"));
+        assert!(!is_plain_symbol("Upper"));
+        assert!(is_plain_symbol("\"A distinct object\""));
+    }
+
+    #[test]
+    fn format_clause_1() {
+        let mut ri = RenamingInfo::new();
+        let p = ri.get_function_id("p".to_owned(), 1, true);
+        let f = ri.get_function_id("f".to_owned(), 2, false);
+        let a = ri.get_function_id("a".to_owned(), 0, false);
+        let x = Term::new_variable(-5);
+        let y = Term::new_variable(-3);
+        let a = Term::new_constant(a);
+        let f_y_x = Term::new_function(f, vec![y.clone(), x.clone()]);
+        let p_x = Term::new_special_function(p, vec![x]);
+
+        let l1 = Literal::new(true, p_x, Term::new_truth());
+        let l2 = Literal::new(false, f_y_x, a.clone());
+        let l3 = Literal::new(true, y, a);
+        let cl = Clause::new(vec![l1, l2, l3]);
+
+        assert_eq!(
+            format_clause(&ri, &cl),
+            "( ~p(X0) | f(X1,X0) = a | X1 != a )"
+        );
+        assert_eq!(format_clause(&ri, &Clause::new(Vec::new())), "$false");
+    }
+
+    #[test]
+    fn saturation_to_tstp_1() {
+        let mut ri = RenamingInfo::new();
+        let a = ri.get_function_id("a".to_owned(), 0, false);
+        let b = ri.get_function_id("b".to_owned(), 0, false);
+        let mut cl = Clause::new(vec![Literal::new(
+            true,
+            Term::new_constant(a),
+            Term::new_constant(b),
+        )]);
+        cl.set_id(7);
+
+        assert_eq!(
+            saturation_to_tstp(&ri, &[&cl]),
+            vec!["cnf(c_7,plain,( a != b ))."]
+        );
+    }
+}
@@ -32,7 +32,7 @@ pub fn mgu(s: &Term, t: &Term) -> Option<Substitution> {
             if s.get_id() == t.get_id() {
                 assert_eq!(s.get_arity(), t.get_arity());
                 // decompose
-                eqs.extend(s.into_iter().zip(t.into_iter()));
+                eqs.extend(s.into_iter().zip(t));
             } else {
                 return None; // conflict
             }
@@ -46,7 +46,7 @@ pub fn mgu(s: &Term, t: &Term) -> Option<Substitution> {
                 return None; // check
             } else {
                 // Can't unify between two different sorts.
-                if t.is_special_function() {
+                if t.get_sort() != s.get_sort() {
                     return None;
                 }
 
@@ -255,4 +255,18 @@ mod test {
         assert_eq!(sigma.get(&x), Some(&f_y));
         assert_eq!(sigma.get(&z), Some(&g_f_y));
     }
+
+    #[test]
+    fn mgu_14_sort() {
+        // f(x) = f(c) where x and c have different sorts
+        let x = Term::new_sorted_variable(-1, 2);
+        let y = Term::new_sorted_variable(-2, 3);
+        let c = Term::new_sorted_function(2, 3, Vec::new());
+        let f_x = Term::new_function(1, vec![x.clone()]);
+        let f_c = Term::new_function(1, vec![c.clone()]);
+
+        assert!(mgu(&f_x, &f_c).is_none());
+        assert!(mgu(&x, &y).is_none());
+        assert!(mgu(&y, &c).is_some());
+    }
 }
@@ -39,16 +39,21 @@ pub fn term_match_general(
     mut eqs: Vec<(Term, Term)>,
 ) -> Option<Substitution> {
     while let Some((s, t)) = eqs.pop() {
-        if s.is_function() && t.is_function() {
+        if s.is_ground() {
+            // A ground term only matches itself.
+            if s != t {
+                return None;
+            }
+        } else if s.is_function() && t.is_function() {
             if s.get_id() == t.get_id() {
                 assert_eq!(s.get_arity(), t.get_arity());
-                eqs.extend(s.into_iter().zip(t.into_iter()));
+                eqs.extend(s.into_iter().zip(t));
             } else {
                 return None;
             }
         } else if s.is_variable() {
             // Can't unify between two different sorts.
-            if t.is_special_function() {
+            if t.get_sort() != s.get_sort() {
                 return None;
             }
 
@@ -105,4 +110,13 @@ mod test {
         let g_x = Term::new_function(2, vec![x]);
         assert!(term_match(&f_x, &g_x).is_none());
     }
+
+    #[test]
+    fn term_match_4() {
+        // f(x) = f(c) where x and c have different sorts
+        let x = Term::new_sorted_variable(-1, 2);
+        let f_x = Term::new_function(1, vec![x]);
+        let f_c = Term::new_function(1, vec![Term::new_sorted_function(2, 3, Vec::new())]);
+        assert!(term_match(&f_x, &f_c).is_none());
+    }
 }
@@ -56,7 +56,7 @@ impl Substitution {
     }
 
     /// Hack, remove.
-    pub fn iter_mut(&mut self) -> IterMut<i64, Term> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, i64, Term> {
         self.subst.iter_mut()
     }
 }
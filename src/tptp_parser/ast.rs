@@ -14,6 +14,8 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
+use std::fmt::{self, Display, Formatter};
+
 /// A TPTP input is either an annotated formula or an include.
 #[derive(Debug)]
 #[allow(variant_size_differences, missing_docs)]
@@ -28,14 +30,72 @@ pub enum TptpInput {
 pub enum AnnotatedFormula {
     Cnf(CnfAnnotated),
     Fof(FofAnnotated),
+    Tff(TffAnnotated),
 }
 
 /// The first part is the name, the second is the formula role, the third is the actual formula.
-/// The fourth part is missing because I don't know what to do with it even if it exists.
-pub type CnfAnnotated = (String, String, Formula);
+/// The fourth part contains the annotations, if there are any.
+pub type CnfAnnotated = (String, String, Formula, Option<Annotations>);
 
 /// Similar to the above.
-pub type FofAnnotated = (String, String, Formula);
+pub type FofAnnotated = (String, String, Formula, Option<Annotations>);
+
+/// Similar to the above, except that the formula can also be a type declaration.
+pub type TffAnnotated = (String, String, TffFormula, Option<Annotations>);
+
+/// An annotated formula together with the name of the file it was read from, if any.
+pub type InputFormula = (AnnotatedFormula, Option<String>);
+
+/// The source of a formula and the optional list of useful info.
+pub type Annotations = (GeneralTerm, Option<Vec<GeneralTerm>>);
+
+/// The general terms used in annotations.
+/// Atomic data is kept exactly as written, including the quotes.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+#[allow(missing_docs)]
+pub enum GeneralTerm {
+    Data(String),
+    Function(String, Vec<GeneralTerm>),
+    Colon(Box<GeneralTerm>, Box<GeneralTerm>),
+    List(Vec<GeneralTerm>),
+}
+
+impl Display for GeneralTerm {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            GeneralTerm::Data(ref s) => write!(f, "{}", s),
+            GeneralTerm::Function(ref s, ref args) => {
+                write!(f, "{}({})", s, join_general_terms(args))
+            }
+            GeneralTerm::Colon(ref d, ref t) => write!(f, "{}:{}", d, t),
+            GeneralTerm::List(ref l) => write!(f, "[{}]", join_general_terms(l)),
+        }
+    }
+}
+
+fn join_general_terms(l: &[GeneralTerm]) -> String {
+    l.iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A TFF formula is either a normal formula or a type declaration of a symbol.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+#[allow(missing_docs)]
+pub enum TffFormula {
+    Formula(Formula),
+    TypeDeclaration(String, TffType),
+}
+
+/// The type given to a symbol in a type declaration.
+/// Atomic types are used for sorts and constants, mapping types for functions and predicates.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+#[allow(missing_docs)]
+pub enum TffType {
+    Atomic(String),
+    Mapping(Vec<String>, String),
+}
 
 /// The first part is the path.
 /// The second optional part is a vector of the names of formulas to take from that file.
@@ -50,15 +110,18 @@ pub enum Term {
 }
 
 /// Data type for FOL formulas.
+/// Quantifiers contain the name of the bound variable and its sort, which is `$i` for untyped variables.
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 #[allow(missing_docs)]
 pub enum Formula {
+    True,
+    False,
     Predicate(String, Vec<Term>),
     Not(Box<Formula>),
     And(Box<Formula>, Box<Formula>),
     Or(Box<Formula>, Box<Formula>),
     Implies(Box<Formula>, Box<Formula>),
     Equivalent(Box<Formula>, Box<Formula>),
-    Forall(String, Box<Formula>),
-    Exists(String, Box<Formula>),
+    Forall(String, String, Box<Formula>),
+    Exists(String, String, Box<Formula>),
 }
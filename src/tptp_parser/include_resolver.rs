@@ -0,0 +1,182 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::utils::hash_map::HashMap;
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Decides where the files named in include directives are read from.
+pub trait IncludeResolver: Debug {
+    /// Finds the file with the given name, included from `including_file` if it is known.
+    /// Returns a name which uniquely identifies the file found, along with its contents.
+    fn resolve(&self, name: &str, including_file: Option<&str>)
+        -> Result<(String, String), String>;
+}
+
+/// Reads included files from the file system.
+/// Like the TPTP specification says, the file is first searched relative to the including file
+/// and then relative to the TPTP root directory. As a last resort the current directory is tried.
+#[derive(Debug, Clone)]
+pub struct FileSystemResolver {
+    tptp_root: Option<PathBuf>,
+}
+
+impl Default for FileSystemResolver {
+    fn default() -> FileSystemResolver {
+        FileSystemResolver::new()
+    }
+}
+
+impl FileSystemResolver {
+    /// Creates a resolver which uses the `TPTP` environment variable as the TPTP root directory, if it is set.
+    pub fn new() -> FileSystemResolver {
+        FileSystemResolver {
+            tptp_root: env::var_os("TPTP").map(PathBuf::from),
+        }
+    }
+
+    /// Set the TPTP root directory, overriding the `TPTP` environment variable.
+    pub fn set_tptp_root(&mut self, root: &str) {
+        self.tptp_root = Some(PathBuf::from(root));
+    }
+
+    /// Get the paths where an included file is searched for, in order.
+    fn candidate_paths(&self, name: &str, including_file: Option<&str>) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if let Some(dir) = including_file.and_then(|f| Path::new(f).parent()) {
+            paths.push(dir.join(name));
+        }
+        if let Some(ref root) = self.tptp_root {
+            paths.push(root.join(name));
+        }
+        paths.push(PathBuf::from(name));
+        paths
+    }
+}
+
+impl IncludeResolver for FileSystemResolver {
+    fn resolve(
+        &self,
+        name: &str,
+        including_file: Option<&str>,
+    ) -> Result<(String, String), String> {
+        let paths = self.candidate_paths(name, including_file);
+        if let Some(path) = paths.iter().find(|p| p.is_file()) {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+            let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            Ok((path.display().to_string(), contents))
+        } else {
+            let tried = paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>();
+            Err(format!(
+                "couldn't find {} (tried {})",
+                name,
+                tried.join(", ")
+            ))
+        }
+    }
+}
+
+/// Looks up included files from a set of files kept in memory.
+/// The names are used as is, i.e. they are not relative to the including file.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryResolver {
+    files: HashMap<String, String>,
+}
+
+impl InMemoryResolver {
+    /// Creates a resolver with no files.
+    pub fn new() -> InMemoryResolver {
+        InMemoryResolver::default()
+    }
+
+    /// Adds a file which can then be included with the given name.
+    pub fn add_file(&mut self, name: &str, contents: &str) {
+        self.files.insert(name.to_owned(), contents.to_owned());
+    }
+}
+
+impl IncludeResolver for InMemoryResolver {
+    fn resolve(&self, name: &str, _: Option<&str>) -> Result<(String, String), String> {
+        self.files
+            .get(name)
+            .map(|contents| (name.to_owned(), contents.clone()))
+            .ok_or_else(|| format!("couldn't find {}", name))
+    }
+}
+
+/// Refuses all includes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyIncludes;
+
+impl IncludeResolver for DenyIncludes {
+    fn resolve(&self, name: &str, _: Option<&str>) -> Result<(String, String), String> {
+        Err(format!("including {} is not allowed", name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DenyIncludes, FileSystemResolver, InMemoryResolver, IncludeResolver};
+
+    #[test]
+    fn in_memory_resolver_1() {
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("Axioms/A.ax", "fof(a, axiom, p).");
+        assert_eq!(
+            resolver.resolve("Axioms/A.ax", None),
+            Ok(("Axioms/A.ax".to_owned(), "fof(a, axiom, p).".to_owned()))
+        );
+        assert!(resolver.resolve("Axioms/B.ax", None).is_err());
+    }
+
+    #[test]
+    fn deny_includes_1() {
+        assert!(DenyIncludes.resolve("test_problems/p1.p", None).is_err());
+    }
+
+    #[test]
+    fn file_system_resolver_1() {
+        let resolver = FileSystemResolver::new();
+        assert!(resolver.resolve("test_problems/p1.p", None).is_ok());
+        assert!(resolver
+            .resolve("test_problems/does_not_exist.p", None)
+            .is_err());
+    }
+
+    #[test]
+    fn file_system_resolver_2() {
+        // Relative to the including file.
+        let resolver = FileSystemResolver::new();
+        let (name, _) = resolver
+            .resolve("CAT003-0.ax", Some("examples/CAT015-3.p"))
+            .expect("The file should be found");
+        assert!(name.ends_with("CAT003-0.ax"));
+    }
+
+    #[test]
+    fn file_system_resolver_3() {
+        // Relative to the TPTP root.
+        let mut resolver = FileSystemResolver::new();
+        resolver.set_tptp_root("examples");
+        assert!(resolver.resolve("GRP004-0.ax", None).is_ok());
+    }
+}
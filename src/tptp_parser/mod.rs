@@ -16,13 +16,19 @@
 
 //! Contains the parser for the TPTP format.
 
-/// Autogenerated code, don't care about any warnings.
+// Autogenerated code, don't care about any warnings.
 lalrpop_mod!(#[allow(clippy::all)] pub parser_grammar, "/tptp_parser/parser_grammar.rs");
 
 /// Contains a function for parsing a formula in the TPTP format to the abstract syntax tree.
-#[cfg_attr(rustfmt, rustfmt_skip)]
+#[rustfmt::skip]
 #[cfg_attr(feature="clippy", allow(result_unwrap_used))]
 pub mod parser;
 
 /// The abstract syntax tree the parser constructs.
 pub mod ast;
+
+/// Contains the errors reported for faulty TPTP input.
+pub mod parse_error;
+
+/// Contains different ways of finding the files named in include directives.
+pub mod include_resolver;
@@ -0,0 +1,191 @@
+// Serkr - An automated theorem prover. Copyright (C) 2015-2016 Mikko Aarnos.
+//
+// Serkr is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Serkr is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Serkr. If not, see <http://www.gnu.org/licenses/>.
+//
+
+use lalrpop_util::ParseError;
+use std::fmt;
+
+/// An error found while reading a TPTP problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TptpParseError {
+    /// The problem does not follow the TPTP syntax.
+    Syntax(SyntaxError),
+    /// The problem could not be read or is otherwise faulty, e.g. an included file is missing.
+    Input(String),
+}
+
+impl fmt::Display for TptpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TptpParseError::Syntax(ref e) => write!(f, "{}", e),
+            TptpParseError::Input(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<String> for TptpParseError {
+    fn from(s: String) -> TptpParseError {
+        TptpParseError::Input(s)
+    }
+}
+
+/// The location and the details of a syntax error.
+/// Lines and columns start from one, columns are counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    file: Option<String>,
+    line: usize,
+    column: usize,
+    message: String,
+    expected: Vec<String>,
+    excerpt: String,
+}
+
+impl SyntaxError {
+    /// Converts an error from the generated parser into a syntax error.
+    /// The source should be the exact text given to the parser.
+    pub fn from_lalrpop<T, E>(
+        e: ParseError<usize, T, E>,
+        source: &str,
+        file: Option<&str>,
+    ) -> SyntaxError
+    where
+        T: fmt::Display,
+        E: fmt::Display,
+    {
+        let (location, message, expected) = match e {
+            ParseError::InvalidToken { location } => {
+                (location, "invalid token".to_owned(), Vec::new())
+            }
+            ParseError::UnrecognizedEOF { location, expected } => {
+                (location, "unexpected end of input".to_owned(), expected)
+            }
+            ParseError::UnrecognizedToken {
+                token: (l, t, _),
+                expected,
+            } => (l, format!("unexpected token `{}`", t), expected),
+            ParseError::ExtraToken { token: (l, t, _) } => {
+                (l, format!("extra token `{}`", t), Vec::new())
+            }
+            ParseError::User { error } => (0, error.to_string(), Vec::new()),
+        };
+        let (line, column, excerpt) = locate(source, location);
+
+        SyntaxError {
+            file: file.map(|s| s.to_owned()),
+            line,
+            column,
+            message,
+            expected,
+            excerpt,
+        }
+    }
+
+    /// Get the file containing the error, if the input came from a file.
+    pub fn get_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Get the line of the error.
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the column of the error.
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Get the tokens the parser would have accepted at the location of the error.
+    pub fn get_expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    /// Get the line of the source containing the error.
+    pub fn get_excerpt(&self) -> &str {
+        &self.excerpt
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.file {
+            Some(ref file) => write!(f, "{}:{}:{}: ", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+        write!(f, "{}", self.message)?;
+        if !self.expected.is_empty() {
+            write!(f, ", expected one of {}", self.expected.join(", "))?;
+        }
+
+        // Point at the error, keeping tabs so that the marker lines up.
+        let marker = self
+            .excerpt
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        write!(f, "\n{}\n{}^", self.excerpt, marker)
+    }
+}
+
+/// Finds the line, the column and the contents of the line for a byte offset in the source.
+fn locate(source: &str, offset: usize) -> (usize, usize, String) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+
+    let line = before.matches('\n').count() + 1;
+    let column = source[line_start..offset].chars().count() + 1;
+    let excerpt = source[line_start..line_end]
+        .trim_end_matches('\r')
+        .to_owned();
+    (line, column, excerpt)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{locate, SyntaxError};
+    use lalrpop_util::ParseError;
+
+    #[test]
+    fn locate_1() {
+        let source = "fof(a, axiom, p).\nfof(b, axiom, q)).\n";
+        assert_eq!(locate(source, 0), (1, 1, "fof(a, axiom, p).".to_owned()));
+        assert_eq!(locate(source, 34), (2, 17, "fof(b, axiom, q)).".to_owned()));
+        assert_eq!(locate(source, source.len()), (3, 1, "".to_owned()));
+    }
+
+    #[test]
+    fn syntax_error_display_1() {
+        let source = "fof(a, axiom, p).\n\tfof(b, axiom, q)).";
+        let e: ParseError<usize, &str, &str> = ParseError::UnrecognizedToken {
+            token: (35, ")", 36),
+            expected: vec!["\"fof\"".to_owned(), "\"cnf\"".to_owned()],
+        };
+        let e = SyntaxError::from_lalrpop(e, source, Some("a.p"));
+        assert_eq!(e.get_line(), 2);
+        assert_eq!(e.get_column(), 18);
+        assert_eq!(
+            e.to_string(),
+            "a.p:2:18: unexpected token `)`, expected one of \"fof\", \"cnf\"\n\
+             \tfof(b, axiom, q)).\n\
+             \t                ^"
+        );
+    }
+}
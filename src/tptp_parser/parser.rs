@@ -18,10 +18,13 @@
 extern crate regex;
 
 
+use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 use crate::tptp_parser::ast::*;
+use crate::tptp_parser::include_resolver::IncludeResolver;
+use crate::tptp_parser::parse_error::{SyntaxError, TptpParseError};
 //use tptp_parser::parser_grammar::parse_TPTP_file;
 
 /// Checks if a character is a double quote. 
@@ -31,24 +34,30 @@ fn is_double_quote(c: char) -> bool {
     c as u8 == 34
 }
 
+/// Replaces a piece of text with spaces, keeping the line breaks and the length in bytes intact.
+fn blank_out(s: &str) -> String {
+    s.chars().map(|c| if c == '\n' { "\n".to_owned() } else { " ".repeat(c.len_utf8()) }).collect()
+}
+
 /// Used for removing all comments from the file parsed in.
+/// The comments are replaced with whitespace so that the locations in the file stay the same.
 fn remove_comments(s: &str) -> String {
     // First remove all comment blocks.
     let comment_block_regex = regex::Regex::new(r"[/][*]([^*]*[*][*]*[^/*])*[^*]*[*][*]*[/]").expect("This should always work");
-    let s2 = comment_block_regex.replace_all(s, regex::NoExpand(""));
-    let mut s3 = "".to_owned();
+    let s2 = comment_block_regex.replace_all(s, |caps: &regex::Captures| blank_out(&caps[0]));
+    let mut lines = Vec::new();
     
     // Then remove all comment lines. 
     // This is a bit tricky due to the possibility of single-quoted and double-quoted strings.
     // Escaping is also annoying.
     // All in all this part most likely has some subtle bugs.
-    for l in s2.lines() {
+    for l in s2.split('\n') {
         let mut inside_single_quoted = false;
         let mut inside_double_quoted = false;
         let mut comment_start_location = None;
         let mut escaping_next = false;
         
-        for (i, c) in l.chars().enumerate() {
+        for (i, c) in l.char_indices() {
             assert!(!inside_single_quoted || !inside_double_quoted);
             
             if (inside_single_quoted || inside_double_quoted) && c == '\\' {
@@ -74,63 +83,60 @@ fn remove_comments(s: &str) -> String {
         
         // Did we find a comment? If so remove it.
         if let Some(pos) = comment_start_location {
-            s3.push_str(&l[0..pos]);
+            lines.push(format!("{}{}", &l[0..pos], blank_out(&l[pos..])));
         } else {
-            s3.push_str(l);
+            lines.push(l.to_owned());
         }
     }
     
-    s3
-}
-
-/// Remove all empty lines (i.e. containing only whitespace) from the file passed in.
-/// Not sure if this is necessary.
-fn remove_empty_lines(s: &str) -> String {
-    let empty_line_regex = regex::Regex::new(r"[ ]*[\n]").expect("This should always work");
-    empty_line_regex.replace_all(s, regex::NoExpand("")).into_owned()
+    lines.join("\n")
 }
 
 /// Reads the file at the location given into a String.
-fn read_file(s: &str) -> Result<String, String> {
+fn read_file(s: &str) -> Result<String, TptpParseError> {
     let path = Path::new(s);
     let display = path.display();
 
     // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
+    let mut file = match File::open(path) {
         // The `description` method of `io::Error` returns a string that
         // describes the error
-        Err(why) => return Err(format!("couldn't open {}: {}", display, &why)),
+        Err(why) => return Err(TptpParseError::Input(format!("couldn't open {}: {}", display, &why))),
         Ok(file) => file,
     };
 
     // Read the file contents into a string, returns `io::Result<usize>`
     let mut f = String::new();
     match file.read_to_string(&mut f) {
-        Err(why) => Err(format!("couldn't read {}: {}", display, &why)),
+        Err(why) => Err(TptpParseError::Input(format!("couldn't read {}: {}", display, &why))),
         Ok(_) => Ok(f),
     }
 }
 
-/// Reads the file at the location given into a String, and preprocesses it into a more suitable form for the parser.
-fn read_and_preprocess_file(s: &str) -> Result<String, String> {
-    let s2 = read_file(s)?;
-    let s3 = remove_comments(&s2);
-    Ok(remove_empty_lines(&s3))
-}
-
 /// Hacky way to see if an annotated formula has the same name as some string.
 fn annotated_formula_names_match(af: &AnnotatedFormula, s: &str) -> bool {
     match *af { 
         AnnotatedFormula::Cnf(ref f) | 
         AnnotatedFormula::Fof(ref f) => f.0 == s,
+        AnnotatedFormula::Tff(ref f) => f.0 == s,
     } 
 }
 
 /// Handles an include directive. Includes work pretty much like in C, just paste the file to where the include was.
-fn handle_include(incl: Include) -> Result<Vec<AnnotatedFormula>, String> {
-    let include_file = parse_tptp_file(&incl.0)?;
+/// The include stack contains the files currently being parsed, it is used for detecting include cycles.
+/// The formulas read from the file are marked as coming from the file with the name used in the include.
+fn handle_include(incl: Include, resolver: &dyn IncludeResolver, include_stack: &mut Vec<String>) -> Result<Vec<InputFormula>, TptpParseError> {
+    let (name, contents) = resolver.resolve(&incl.0, include_stack.last().map(|s| s.as_str()))?;
+    if include_stack.contains(&name) {
+        return Err(TptpParseError::Input(format!("Include cycle detected: {} -> {}", include_stack.join(" -> "), name)));
+    }
+    
+    include_stack.push(name);
+    let include_file = parse_tptp_input(&contents, resolver, include_stack, Some(&incl.0));
+    include_stack.pop();
+    let include_file = include_file?;
     if let Some(formulae) = incl.1 {
-        Ok(include_file.into_iter().filter(|input| formulae.iter().any(|s| annotated_formula_names_match(input, s))).collect())
+        Ok(include_file.into_iter().filter(|input| formulae.iter().any(|s| annotated_formula_names_match(&input.0, s))).collect())
     } else {
         Ok(include_file)
     }
@@ -138,48 +144,84 @@ fn handle_include(incl: Include) -> Result<Vec<AnnotatedFormula>, String> {
 
 use crate::tptp_parser;
 /// Parses a file in TPTP format to a vector of annotated formulae.
-#[cfg_attr(feature="clippy", allow(use_debug))]
-pub fn parse_tptp_file(s: &str) -> Result<Vec<AnnotatedFormula>, String> {
-    let preprocessed_file = read_and_preprocess_file(s)?;
+/// Included files are found with the resolver given.
+pub fn parse_tptp_file(s: &str, resolver: &dyn IncludeResolver) -> Result<Vec<InputFormula>, TptpParseError> {
+    let contents = read_file(s)?;
+    let name = fs::canonicalize(s).map(|p| p.display().to_string()).unwrap_or_else(|_| s.to_owned());
+    parse_tptp_input(&contents, resolver, &mut vec![name], Some(s))
+}
+
+/// Parses a problem in TPTP format given as a string to a vector of annotated formulae.
+/// Included files are found with the resolver given.
+pub fn parse_tptp_str(s: &str, resolver: &dyn IncludeResolver) -> Result<Vec<InputFormula>, TptpParseError> {
+    parse_tptp_input(s, resolver, &mut Vec::new(), None)
+}
+
+/// Parses TPTP input, the last element of the include stack is the file the input is from (if any).
+/// The formulas are marked as coming from the file with the given name.
+fn parse_tptp_input(s: &str, resolver: &dyn IncludeResolver, include_stack: &mut Vec<String>, file: Option<&str>) -> Result<Vec<InputFormula>, TptpParseError> {
+    let preprocessed_file = remove_comments(s);
     let file_parser = tptp_parser::parser_grammar::TPTP_fileParser::new();
-    let parsed_file = file_parser.parse(&preprocessed_file).map_err(|x| format!("{:?}", x))?;
+    let parsed_file = file_parser.parse(&preprocessed_file).map_err(|e| {
+        let file = include_stack.last().map(|f| f.as_str());
+        TptpParseError::Syntax(SyntaxError::from_lalrpop(e, s, file))
+    })?;
     
     // Handle all includes.
-    let mut formulas = Vec::<AnnotatedFormula>::new(); 
+    let mut formulas = Vec::<InputFormula>::new(); 
     for input in parsed_file {
         match input {
-            TptpInput::AnnForm(f) => formulas.push(f),
-            TptpInput::Incl(i) => formulas.append(&mut handle_include(i)?),
+            TptpInput::AnnForm(f) => formulas.push((f, file.map(|s| s.to_owned()))),
+            TptpInput::Incl(i) => formulas.append(&mut handle_include(i, resolver, include_stack)?),
         }
     }
     
     Ok(formulas)
 }
 
+#[cfg(test)]
 fn parse_cnf_annotated(s: &str) -> Result<(String, String, Formula), String> {
     let p = tptp_parser::parser_grammar::cnf_annotatedParser::new();
+    let r = p.parse(s).map(|(n, fr, f, _)| (n, fr, f)).map_err(|x| format!("{:?}", x));
+    r
+}
+
+#[cfg(test)]
+fn parse_fof_annotated(s: &str) -> Result<FofAnnotated, String> {
+    let p = tptp_parser::parser_grammar::fof_annotatedParser::new();
     let r = p.parse(s).map_err(|x| format!("{:?}", x));
     r
 }
 
+#[cfg(test)]
+fn parse_tff_annotated(s: &str) -> Result<(String, String, TffFormula), String> {
+    let p = tptp_parser::parser_grammar::tff_annotatedParser::new();
+    let r = p.parse(s).map(|(n, fr, f, _)| (n, fr, f)).map_err(|x| format!("{:?}", x));
+    r
+}
+
+#[cfg(test)]
 fn parse_single_quoted(s: &str) -> Result<String, String> {
     let p = tptp_parser::parser_grammar::single_quotedParser::new();
     let r = p.parse(s).map_err(|x| format!("{:?}", x));
     r
 }
 
+#[cfg(test)]
 fn parse_include(s: &str) -> Result<(String, Option<Vec<String>>), String> {
     let p = tptp_parser::parser_grammar::includeParser::new();
     let r = p.parse(s).map_err(|x| format!("{:?}", x));
     r
 }
 
+#[cfg(test)]
 fn parse_distinct_object(s: &str) -> Result<String, String> {
     let p = tptp_parser::parser_grammar::distinct_objectParser::new();
     let r = p.parse(s).map_err(|x| format!("{:?}", x));
     r
 }
 
+#[cfg(test)]
 fn parse_dollar_word(s: &str) -> Result<String, String> {
     let p = tptp_parser::parser_grammar::dollar_wordParser::new();
     let r = p.parse(s).map_err(|x| format!("{:?}", x));
@@ -190,8 +232,12 @@ fn parse_dollar_word(s: &str) -> Result<String, String> {
 mod test {
     
     use crate::tptp_parser::ast::*;   
-    use super::parse_tptp_file;
+    use super::{parse_tptp_file, parse_tptp_str};
+    use crate::tptp_parser::include_resolver::{DenyIncludes, FileSystemResolver, InMemoryResolver};
+    use crate::tptp_parser::parse_error::TptpParseError;
     use super::parse_cnf_annotated;
+    use super::parse_fof_annotated;
+    use super::parse_tff_annotated;
     use super::parse_single_quoted;
     use super::parse_include;
     use super::parse_distinct_object;
@@ -200,23 +246,82 @@ mod test {
 
     #[test]
     fn parser_test_0() {
-        assert!(parse_tptp_file("examples/SET060-6.p").is_ok());
+        assert!(parse_tptp_file("examples/SET060-6.p", &FileSystemResolver::new()).is_ok());
     }
 
     #[test]
     fn parser_test_1() {
-        assert!(parse_tptp_file("test_problems/SYN000-1.p").is_ok());
+        assert!(parse_tptp_file("test_problems/SYN000-1.p", &FileSystemResolver::new()).is_ok());
     }
     
     #[test]
     fn parser_test_2() {
-        assert!(parse_tptp_file("test_problems/SYN000+1.p").is_ok());
+        assert!(parse_tptp_file("test_problems/SYN000+1.p", &FileSystemResolver::new()).is_ok());
     }
     
     #[test]
     fn parser_test_3() {
         // Try to read a file which does not exist.
-        assert!(parse_tptp_file("test_problems/does_not_exists.p").is_err());
+        assert!(parse_tptp_file("test_problems/does_not_exists.p", &FileSystemResolver::new()).is_err());
+    }
+    
+    #[test]
+    fn parser_test_4() {
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("Axioms/A.ax", "fof(a1, axiom, p). fof(a2, axiom, q).");
+        let problem = "include('Axioms/A.ax', [a2]).\n% A comment.\nfof(c, conjecture, q).";
+        let formulas = parse_tptp_str(problem, &resolver).expect("Parsing should succeed");
+        assert_eq!(formulas.len(), 2);
+        assert_eq!(formulas[0].1, Some("Axioms/A.ax".to_owned()));
+        assert_eq!(formulas[1].1, None);
+        assert!(parse_tptp_str("include('Axioms/B.ax').", &resolver).is_err());
+    }
+    
+    #[test]
+    fn parser_test_6() {
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("A.ax", "include('B.ax'). fof(a, axiom, p).");
+        resolver.add_file("B.ax", "include('A.ax'). fof(b, axiom, q).");
+        let res = parse_tptp_str("include('A.ax').", &resolver);
+        assert_eq!(res.err(), Some(TptpParseError::Input("Include cycle detected: A.ax -> B.ax -> A.ax".to_owned())));
+    }
+    
+    #[test]
+    fn parser_test_7() {
+        // The same file can be included several times as long as there is no cycle.
+        let mut resolver = InMemoryResolver::new();
+        resolver.add_file("A.ax", "fof(a, axiom, p).");
+        let formulas = parse_tptp_str("include('A.ax'). include('A.ax').", &resolver).expect("Parsing should succeed");
+        assert_eq!(formulas.len(), 2);
+    }
+    
+    #[test]
+    fn parser_test_8() {
+        // Comments should not change the location of syntax errors.
+        let problem = "fof(a, axiom, p). % A comment.\n/* A comment\n block. */ fof(b, axiom, q)).";
+        match parse_tptp_str(problem, &DenyIncludes) {
+            Err(TptpParseError::Syntax(e)) => {
+                assert_eq!(e.get_line(), 3);
+                assert_eq!(e.get_column(), 28);
+                assert_eq!(e.get_excerpt(), " block. */ fof(b, axiom, q)).");
+                assert_eq!(e.get_expected(), &["\".\"".to_owned()]);
+            }
+            _ => panic!("Expected a syntax error"),
+        }
+    }
+    
+    #[test]
+    fn parser_test_9() {
+        match parse_tptp_str("include('A.ax').", &DenyIncludes) {
+            Err(TptpParseError::Input(_)) => {}
+            _ => panic!("Expected an input error"),
+        }
+    }
+    
+    #[test]
+    fn parser_test_5() {
+        assert!(parse_tptp_str("fof(a, axiom, p).", &DenyIncludes).is_ok());
+        assert!(parse_tptp_str("include('Axioms/A.ax').", &DenyIncludes).is_err());
     }
     
     
@@ -282,9 +387,7 @@ mod test {
     fn parse_cnf_annotated_true_false() {
         let res = parse_cnf_annotated("cnf(true_false,axiom,( $true | $false )).");
         
-        let t = Formula::Predicate("$true".to_owned(), Vec::new());
-        let f = Formula::Predicate("$false".to_owned(), Vec::new());
-        let fm = Formula::Or(Box::new(t), Box::new(f));
+        let fm = Formula::Or(Box::new(Formula::True), Box::new(Formula::False));
         
         assert_eq!(res.unwrap(), ("true_false".to_owned(), "axiom".to_owned(), fm));
     }
@@ -307,8 +410,8 @@ mod test {
     fn parse_cnf_annotated_distinct_object() {
         let res = parse_cnf_annotated("cnf(distinct_object,axiom,( \"An Apple\" != \"A \\\"Microsoft \\ escape\\\"\" )).");
         
-        let fst_p = Term::Function("An Apple".to_owned(), Vec::new());
-        let snd_p = Term::Function("A \\\"Microsoft \\ escape\\\"".to_owned(), Vec::new());
+        let fst_p = Term::Function("\"An Apple\"".to_owned(), Vec::new());
+        let snd_p = Term::Function("\"A \\\"Microsoft \\ escape\\\"\"".to_owned(), Vec::new());
         let fm = Formula::Not(Box::new(Formula::Predicate("=".to_owned(), vec!(fst_p, snd_p))));
         
         assert_eq!(res.unwrap(), ("distinct_object".to_owned(), "axiom".to_owned(), fm));
@@ -360,6 +463,49 @@ mod test {
         assert_eq!(res, ("reals".to_owned(), "axiom".to_owned(), fm));
     }
   
+    #[test]
+    fn parse_tff_annotated_type_declarations() {
+        let res = parse_tff_annotated("tff(animal_type,type,animal: $tType).").unwrap();
+        assert_eq!(res.2, TffFormula::TypeDeclaration("animal".to_owned(), TffType::Atomic("$tType".to_owned())));
+        
+        let res = parse_tff_annotated("tff(f_type,type,( f: ( animal * $int ) > animal )).").unwrap();
+        assert_eq!(res.2, TffFormula::TypeDeclaration("f".to_owned(), TffType::Mapping(vec!("animal".to_owned(), "$int".to_owned()), "animal".to_owned())));
+        
+        let res = parse_tff_annotated("tff(p_type,type,p: animal > $o).").unwrap();
+        assert_eq!(res.2, TffFormula::TypeDeclaration("p".to_owned(), TffType::Mapping(vec!("animal".to_owned()), "$o".to_owned())));
+    }
+    
+    #[test]
+    fn parse_tff_annotated_typed_quantifier() {
+        let res = parse_tff_annotated("tff(typed,axiom,! [X: animal, Y] : p(X, Y)).").unwrap();
+        
+        let p_x_y = Formula::Predicate("p".to_owned(), vec!(Term::Variable("X".to_owned()), Term::Variable("Y".to_owned())));
+        let fm = Formula::Forall("Y".to_owned(), "$i".to_owned(), Box::new(Formula::Forall("X".to_owned(), "animal".to_owned(), Box::new(p_x_y))));
+        
+        assert_eq!(res, ("typed".to_owned(), "axiom".to_owned(), TffFormula::Formula(fm)));
+    }
+    
+    #[test]
+    fn parse_fof_annotated_typed_quantifier() {
+        assert!(parse_fof_annotated("fof(a,axiom,![X:$int]: p(X)).").is_err());
+        assert!(parse_fof_annotated("fof(a,axiom,![X]: p(X)).").is_ok());
+    }
+    
+    #[test]
+    fn parse_fof_annotated_annotations() {
+        let res = parse_fof_annotated("fof(a,axiom,p,file('Axioms/A.ax',a_1),[description(\"An axiom\"),status:thm,[]]).").unwrap();
+        
+        let source = GeneralTerm::Function("file".to_owned(), vec!(GeneralTerm::Data("'Axioms/A.ax'".to_owned()), GeneralTerm::Data("a_1".to_owned())));
+        let info = vec!(GeneralTerm::Function("description".to_owned(), vec!(GeneralTerm::Data("\"An axiom\"".to_owned()))),
+                        GeneralTerm::Colon(Box::new(GeneralTerm::Data("status".to_owned())), Box::new(GeneralTerm::Data("thm".to_owned()))),
+                        GeneralTerm::List(Vec::new()));
+        
+        assert_eq!(res.3, Some((source.clone(), Some(info))));
+        assert_eq!(source.to_string(), "file('Axioms/A.ax',a_1)");
+        assert_eq!(parse_fof_annotated("fof(a,axiom,p,unknown).").unwrap().3, Some((GeneralTerm::Data("unknown".to_owned()), None)));
+        assert_eq!(parse_fof_annotated("fof(a,axiom,p).").unwrap().3, None);
+    }
+    
     #[test]
     fn parse_include_test() {
         assert_eq!(parse_include("include('Axioms/SYN000-0.ax').").unwrap(), ("Axioms/SYN000-0.ax".to_owned(), None));
@@ -14,8 +14,11 @@
 // along with Serkr. If not, see <http://www.gnu.org/licenses/>.
 //
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 
 /// A `HashMap` with the Fowler-Noll-Vo hash function.
 /// It is more suited for small integer keys than `SipHash`.
 pub type HashMap<K, V> = FnvHashMap<K, V>;
+
+/// A `HashSet` with the Fowler-Noll-Vo hash function.
+pub type HashSet<K> = FnvHashSet<K>;
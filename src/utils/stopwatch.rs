@@ -66,7 +66,7 @@ impl Stopwatch {
         } else {
             self.stop_time
         } - self.start_time;
-        elapsed_duration.as_secs() * 1000 + (elapsed_duration.subsec_nanos() / 1000000) as u64
+        elapsed_duration.as_secs() * 1000 + elapsed_duration.subsec_millis() as u64
     }
 }
 
@@ -0,0 +1 @@
+tff(three_less_than_two, conjecture, $less($quotient(6.0, 2.0), $to_real(2))).
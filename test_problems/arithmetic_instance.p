@@ -0,0 +1,3 @@
+tff(p_type, type, p: $int > $o).
+tff(p_holds_everywhere, axiom, ! [X: $int] : p(X)).
+tff(p_holds_for_the_sum, conjecture, p($sum(2, 3))).
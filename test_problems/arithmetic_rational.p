@@ -0,0 +1,3 @@
+tff(half_type, type, half: $rat).
+tff(half_def, axiom, half = $quotient(1/1, 2/1)).
+tff(half_plus_half, conjecture, ( $is_int($sum(half, half)) & $greatereq($uminus($sum(half, half)), -1/1) )).
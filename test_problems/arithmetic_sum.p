@@ -0,0 +1 @@
+tff(sum_is_five, conjecture, $sum(2, 3) = 5).
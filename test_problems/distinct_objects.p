@@ -0,0 +1,2 @@
+fof(colour_of_apple, axiom, colour(apple) = "red").
+fof(apple_is_not_green, conjecture, colour(apple) != "green").
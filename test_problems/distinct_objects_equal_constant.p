@@ -0,0 +1,3 @@
+fof(a,axiom,c = "x").
+fof(b,axiom,c = "y").
+fof(g,conjecture,$false).
@@ -0,0 +1,3 @@
+fof(abc_distinct, axiom, $distinct(a, b, c)).
+fof(f_maps_to_abc, axiom, ![X]: (f(X) = a | f(X) = b)).
+fof(f_never_c, conjecture, ![X]: (f(X) != c & $true)).
@@ -0,0 +1,10 @@
+% Every animal has a human owner, so the owner of the cat is human.
+tff(animal_type, type, animal: $tType).
+tff(cat_type, type, cat: animal).
+tff(dog_type, type, dog: animal).
+tff(owner_of_type, type, owner_of: animal > $i).
+tff(human_type, type, human: $i > $o).
+tff(chases_type, type, chases: ( animal * animal ) > $o).
+tff(owners_are_human, axiom, ! [A: animal] : human(owner_of(A))).
+tff(dog_chases_cat, axiom, chases(dog, cat)).
+tff(owner_of_cat_is_human, conjecture, ? [A: animal] : ( chases(dog, A) & human(owner_of(A)) )).
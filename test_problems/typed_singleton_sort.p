@@ -0,0 +1,7 @@
+% The sort a has only one element, but that says nothing about the individuals b1 and b2.
+% Without sorts the axiom would make the conjecture trivially true.
+tff(a_type, type, a: $tType).
+tff(b1_type, type, b1: $i).
+tff(b2_type, type, b2: $i).
+tff(a_is_singleton, axiom, ! [X: a, Y: a] : X = Y).
+tff(b1_is_b2, conjecture, b1 = b2).